@@ -0,0 +1,156 @@
+use ijson::IValue;
+
+use crate::{Settings, Typing};
+
+/// Infers a typing from items pushed one at a time. Items are inferred by chunks that are
+/// merged into the typing built so far, so at most `settings.chunk_size` items are held in
+/// memory.
+/// ## Examples
+/// ```
+/// use ijson::ijson;
+/// use json_typings::{Settings, TypingAccumulator};
+///
+/// let settings = Settings {
+///     chunk_size: 1,
+///     ..Settings::default()
+/// };
+/// let mut accumulator = TypingAccumulator::new("Example", &settings);
+/// accumulator.push(ijson!({ "a": true, "b": 1 }));
+/// accumulator.push(ijson!({ "a": false }));
+///
+/// assert_eq!(
+///     format!("{}", accumulator.finish()),
+///     [
+///         "export interface Example {",
+///         "    a: boolean;",
+///         "    b?: number;",
+///         "}",
+///     ]
+///     .join("\n")
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct TypingAccumulator {
+    name: String,
+    settings: Settings,
+    items: Vec<IValue>,
+    typing: Option<Typing>,
+}
+
+impl TypingAccumulator {
+    pub fn new(name: &str, settings: &Settings) -> Self {
+        Self {
+            name: String::from(name),
+            settings: settings.clone(),
+            items: vec![],
+            typing: None,
+        }
+    }
+
+    pub fn push(&mut self, item: IValue) {
+        self.items.push(item);
+
+        if self.items.len() >= self.settings.chunk_size.max(1) {
+            self.flush();
+        }
+    }
+
+    /// Typing of every item pushed so far
+    pub fn finish(mut self) -> Typing {
+        self.flush();
+
+        let (name, settings) = (self.name, self.settings);
+        match self.typing {
+            Some(mut typing) => {
+                // Chunks are merged without dropping the nodes they made unreachable
+                typing.rebuild(|nx| nx);
+                typing
+            }
+            None => Typing::from_items_with_settings(&name, Vec::new(), &settings),
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+
+        let items = std::mem::take(&mut self.items);
+        let chunk = Typing::from_items_with_settings(&self.name, items, &self.settings);
+        match &mut self.typing {
+            Some(typing) => typing.merge_chunk(&chunk, &self.settings),
+            None => self.typing = Some(chunk),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ijson::ijson;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::strategy::Strategy;
+
+    #[test]
+    fn chunked_literals() {
+        let code = |i: u8| format!("{}{}", (b'a' + i / 26) as char, (b'a' + i % 26) as char);
+        let items: Vec<_> = (0..400u16)
+            .map(|i| ijson!({ "code": code((i / 5) as u8) }))
+            .collect();
+        let settings = Settings {
+            chunk_size: 10,
+            literal_max_values: 32,
+            ..Settings::default()
+        };
+
+        let mut accumulator = TypingAccumulator::new("All", &settings);
+        items
+            .iter()
+            .cloned()
+            .for_each(|item| accumulator.push(item));
+        let streamed = accumulator.finish();
+        let typing = Typing::from_items_with_settings("All", items, &settings);
+
+        self::assert_eq!(
+            typing.as_string(Strategy::Family),
+            streamed.as_string(Strategy::Family)
+        );
+        self::assert_eq!(
+            "export interface All {\n    code: string;\n}\n",
+            streamed.as_string(Strategy::Family)
+        );
+    }
+
+    #[test]
+    fn chunked_variants() {
+        let items: Vec<_> = (0..12)
+            .map(|i| match i % 3 {
+                0 => ijson!({ "type": "click", "x": i }),
+                _ => ijson!({ "type": "scroll", "delta": i }),
+            })
+            .collect();
+        let settings = Settings {
+            chunk_size: 4,
+            discriminated_unions: true,
+            ..Settings::default()
+        };
+
+        let mut accumulator = TypingAccumulator::new("All", &settings);
+        items
+            .iter()
+            .cloned()
+            .for_each(|item| accumulator.push(item));
+        let streamed = accumulator.finish();
+        let typing = Typing::from_items_with_settings("All", items, &settings);
+
+        self::assert_eq!(
+            typing.as_string(Strategy::Family),
+            streamed.as_string(Strategy::Family)
+        );
+        self::assert_eq!(typing.graph.node_count(), streamed.graph.node_count());
+        assert!(streamed
+            .as_string(Strategy::Family)
+            .starts_with("export type All = AllClick | AllScroll;"));
+    }
+}
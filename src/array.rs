@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Syntax of array types in typescript outputs
+#[derive(Serialize, Deserialize, Debug, Hash, PartialEq, Eq, Clone)]
+pub enum ArraySyntax {
+    /// `Array<T>`
+    Generic,
+    /// `T[]`, with unions parenthesized as `(A | B)[]`
+    Array,
+    /// `T[]` for keywords and names, `Array<T>` for any other type
+    ArraySimple,
+}
@@ -1,7 +1,20 @@
-use std::{error::Error, fs, path::Path};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
 
 use clap::{App, Arg, crate_version, crate_authors};
-use json_typings::{read_json, strategy::Strategy, validators, Settings, Typing, SETTINGS};
+use convert_case::{Case, Casing};
+use json_typings::{
+    read_json, strategy::Strategy, validators, ArraySyntax, BigIntStyle, FormatStyle, LiteralStyle,
+    NullStyle, Settings, Typing, TypingAccumulator, SETTINGS,
+};
+
+/// Input or output path standing for stdin or stdout
+const STDIO: &str = "-";
 
 fn main() -> Result<(), Box<dyn Error>> {
     let matches = App::new("Json typings")
@@ -34,31 +47,111 @@ fn main() -> Result<(), Box<dyn Error>> {
                 .value_name("SEMVER")
                 .help(
                     "Specify the typescript version to automatically disable incompatible features",
-                ),
+                )
+                .validator(validators::is_typescript_version),
+            Arg::with_name("record_threshold")
+                .long("record_threshold")
+                .value_name("NUMBER")
+                .help("Minimum amount of varying keys for an object to be typed as a Record, 0 only keeps at least 4 keys made mostly of digits such as ids, hashes and dates")
+                .validator(validators::is_number),
+            Arg::with_name("inline_unions")
+                .long("inline_unions")
+                .value_name("NUMBER")
+                .help("Most members of a union written inline instead of as an alias by the tree strategy, 0 disables it")
+                .validator(validators::is_number),
+            Arg::with_name("chunk_size")
+                .long("chunk_size")
+                .value_name("NUMBER")
+                .help("Amount of items inferred at once when streaming")
+                .validator(validators::is_number),
+            Arg::with_name("literal_style")
+                .long("literal_style")
+                .value_name("STYLE")
+                .help("Declares sets of literals as unions, enums, const enums or as const objects")
+                .possible_values(&["union", "enum", "const_enum", "object"]),
+            Arg::with_name("null_style")
+                .long("null_style")
+                .value_name("STYLE")
+                .help("Types keys missing or null in some objects as optional, nullable, both or undefined")
+                .possible_values(&["separate", "optional", "null", "optional_null", "undefined"]),
+            Arg::with_name("array_syntax")
+                .long("array_syntax")
+                .value_name("SYNTAX")
+                .help("Writes arrays as Array<T>, T[] or T[] only for keywords and names")
+                .possible_values(&["generic", "array", "array-simple"]),
+            Arg::with_name("bigint_style")
+                .long("bigint_style")
+                .value_name("STYLE")
+                .help("Types integers javascript numbers can't represent exactly as numbers, bigints, strings or branded strings")
+                .possible_values(&["number", "bigint", "string", "branded"]),
+            Arg::with_name("format_style")
+                .long("format_style")
+                .value_name("STYLE")
+                .help("Renders formatted strings such as dates or uuids as strings, branded strings or strings with a JSDoc @format tag, integers also get a JSDoc tag")
+                .possible_values(&["plain", "branded", "jsdoc"]),
             Arg::with_name("sort")
                 .long("sort")
                 .help("Enable sorting of interface keys"),
+            Arg::with_name("deduplicate")
+                .long("deduplicate")
+                .help("Merges objects with the same shape into a single shared interface"),
+            Arg::with_name("formats")
+                .long("formats")
+                .help("Detects dates, uuids, urls, emails, ips, hex colors and base64 strings instead of typing them as literals"),
+            Arg::with_name("dates")
+                .long("dates")
+                .help("Types dates as chrono types in rust and as coerced dates in zod instead of strings"),
+            Arg::with_name("index_signatures")
+                .long("index_signatures")
+                .help("Writes records as { [key: string]: T } index signatures instead of Record<string, T>"),
+            Arg::with_name("readonly")
+                .long("readonly")
+                .help("Marks properties, arrays, records and tuples as readonly"),
+            Arg::with_name("discriminated_unions")
+                .long("discriminated_unions")
+                .help("Splits objects told apart by a literal key such as a type into one interface per value"),
+            Arg::with_name("number_bounds")
+                .long("number_bounds")
+                .help("Adds the observed minimum and maximum of numbers to the outputs"),
+            Arg::with_name("stream")
+                .long("stream")
+                .help("Reads the input one item at a time, always enabled for .ndjson and .jsonl files"),
+            Arg::with_name("separate")
+                .long("separate")
+                .help("Types every file as its own root named after the file instead of merging them"),
+            Arg::with_name("split")
+                .long("split")
+                .help("Writes one module per top level declaration with imports between them"),
             Arg::with_name("tree")
                 .long("tree")
                 .help("Sets the formating strategy to tree"),
             Arg::with_name("family")
                 .long("family")
                 .help("Sets the formating strategy to family"),
+            Arg::with_name("json_schema")
+                .long("json_schema")
+                .help("Sets the output to a JSON Schema (draft 2020-12) instead of typescript"),
+            Arg::with_name("rust")
+                .long("rust")
+                .help("Sets the output to rust structs with serde attributes instead of typescript"),
+            Arg::with_name("zod")
+                .long("zod")
+                .help("Sets the output to zod schemas along with their inferred types"),
         ])
         .arg(
             Arg::with_name("output")
                 .short("o")
                 .long("output")
                 .value_name("FILE")
-                .help("Sets the output target file")
-                .default_value("index.d.ts"),
+                .help("Sets the output target file, or directory when splitting, - writes to stdout"),
         )
         .arg(
-            Arg::with_name("INPUT_FILE")
-                .help("Sets the input file to use")
+            Arg::with_name("INPUT")
+                .help("Sets the input files, directories or glob patterns to use, - reads stdin")
                 .required(true)
+                .multiple(true)
                 .index(1)
-                .validator_os(validators::file_exists),
+                .validator_os(validators::is_input),
         )
         .get_matches();
 
@@ -78,10 +171,106 @@ fn main() -> Result<(), Box<dyn Error>> {
         SETTINGS.write().typescript_version = String::from(v);
     }
 
+    if let Some(v) = matches.value_of("record_threshold") {
+        SETTINGS.write().record_threshold = v.parse()?;
+    }
+
+    if let Some(v) = matches.value_of("inline_unions") {
+        SETTINGS.write().inline_union_max_members = v.parse()?;
+    }
+
+    if let Some(v) = matches.value_of("chunk_size") {
+        SETTINGS.write().chunk_size = v.parse()?;
+    }
+
+    if let Some(v) = matches.value_of("literal_style") {
+        SETTINGS.write().literal_style = match v {
+            "enum" => LiteralStyle::Enum,
+            "const_enum" => LiteralStyle::ConstEnum,
+            "object" => LiteralStyle::Object,
+            _ => LiteralStyle::Union,
+        };
+    }
+
+    if let Some(v) = matches.value_of("null_style") {
+        SETTINGS.write().null_style = match v {
+            "optional" => NullStyle::Optional,
+            "null" => NullStyle::Null,
+            "optional_null" => NullStyle::OptionalNull,
+            "undefined" => NullStyle::Undefined,
+            _ => NullStyle::Separate,
+        };
+    }
+
+    if let Some(v) = matches.value_of("array_syntax") {
+        SETTINGS.write().array_syntax = match v {
+            "array" => ArraySyntax::Array,
+            "array-simple" => ArraySyntax::ArraySimple,
+            _ => ArraySyntax::Generic,
+        };
+    }
+
+    if let Some(v) = matches.value_of("bigint_style") {
+        SETTINGS.write().bigint_style = match v {
+            "bigint" => BigIntStyle::BigInt,
+            "string" => BigIntStyle::String,
+            "branded" => BigIntStyle::Branded,
+            _ => BigIntStyle::Number,
+        };
+    }
+
+    if let Some(v) = matches.value_of("format_style") {
+        SETTINGS.write().format_style = match v {
+            "branded" => FormatStyle::Branded,
+            "jsdoc" => FormatStyle::JsDoc,
+            _ => FormatStyle::Plain,
+        };
+    }
+
     if matches.is_present("sort") {
         SETTINGS.write().sort = true;
     }
 
+    if matches.is_present("deduplicate") {
+        SETTINGS.write().deduplicate = true;
+    }
+
+    if matches.is_present("formats") {
+        SETTINGS.write().formats = true;
+    }
+
+    if matches.is_present("dates") {
+        SETTINGS.write().dates = true;
+    }
+
+    if matches.is_present("index_signatures") {
+        SETTINGS.write().index_signatures = true;
+    }
+
+    if matches.is_present("readonly") {
+        SETTINGS.write().readonly = true;
+    }
+
+    if matches.is_present("discriminated_unions") {
+        SETTINGS.write().discriminated_unions = true;
+    }
+
+    if matches.is_present("number_bounds") {
+        SETTINGS.write().number_bounds = true;
+    }
+
+    if matches.is_present("stream") {
+        SETTINGS.write().stream = true;
+    }
+
+    if matches.is_present("separate") {
+        SETTINGS.write().separate = true;
+    }
+
+    if matches.is_present("split") {
+        SETTINGS.write().split = true;
+    }
+
     if matches.is_present("family") {
         SETTINGS.write().strategy = Strategy::Family;
     }
@@ -90,29 +279,149 @@ fn main() -> Result<(), Box<dyn Error>> {
         SETTINGS.write().strategy = Strategy::Tree;
     }
 
-    let input = matches.value_of("INPUT_FILE").unwrap();
-    let value = read_json::file(Path::new(input))?;
+    if matches.is_present("json_schema") {
+        SETTINGS.write().strategy = Strategy::JsonSchema;
+    }
+
+    if matches.is_present("rust") {
+        SETTINGS.write().strategy = Strategy::Rust;
+    }
+
+    if matches.is_present("zod") {
+        SETTINGS.write().strategy = Strategy::Zod;
+    }
+
+    let settings = SETTINGS.read().clone();
+
+    // Config files and environment variables aren't checked by the argument validators
+    validators::is_typescript_version(settings.typescript_version.clone())?;
+
+    // Enums and as const objects have a runtime value, declaration files only hold types
+    let declaration_file =
+        matches!(matches.value_of("output"), Some(output) if output.ends_with(".d.ts"));
+    if declaration_file && settings.literal_style.declares_values() {
+        return Err(
+            "The enum and object literal styles declare values, which need a .ts output".into(),
+        );
+    }
 
-    let typings = match &SETTINGS.read().strategy {
-        Strategy::Tree => {
-            vec![Typing::from_items("All", value)]
+    let mut files = vec![];
+    for input in matches.values_of("INPUT").unwrap() {
+        let path = Path::new(input);
+        if input == STDIO {
+            files.push(path.to_path_buf());
+        } else if path.is_dir() {
+            files.extend(read_json::dir(path)?);
+        } else if path.exists() {
+            files.push(path.to_path_buf());
+        } else {
+            let matched: Vec<_> = glob::glob(input)?
+                .filter_map(Result::ok)
+                .filter(|path| path.is_file())
+                .collect();
+            if matched.is_empty() {
+                return Err(format!("No file matches the pattern {}", input).into());
+            }
+
+            files.extend(matched);
         }
-        Strategy::Family => todo!(),
-    };
+    }
+
+    let mut typings: Vec<Typing> = vec![];
+    if settings.separate {
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for file in &files {
+            let name = match file.to_str() {
+                Some(STDIO) => String::from("Stdin"),
+                _ => {
+                    let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+                    stem.to_case(Case::Pascal)
+                }
+            };
+            let count = seen.entry(name.clone()).or_insert(0);
+            *count += 1;
+
+            let name = match count {
+                1 => name,
+                _ => format!("{}{}", name, count),
+            };
+            typings.push(read_typing(file, &name, &settings)?);
+        }
+    } else {
+        for file in &files {
+            let file_typing = read_typing(file, "All", &settings)?;
+
+            match typings.first_mut() {
+                Some(typing) => typing.merge_with_settings(&file_typing, &settings),
+                None => typings.push(file_typing),
+            }
+        }
+    }
 
-    let output = matches.value_of("output").unwrap();
-    let output = Path::new(output);
-    let output = output.with_extension("ts");
+    if typings.is_empty() {
+        return Err("No json file found in the inputs".into());
+    }
+
+    if settings.deduplicate {
+        typings.iter_mut().for_each(Typing::deduplicate);
+    }
+
+    let strategy = settings.strategy.clone();
+    if settings.split {
+        let extension = strategy.extension(&settings);
+        let modules = strategy
+            .to_modules(&typings, &settings)
+            .ok_or("Splitting the output is only supported by typescript outputs")?;
+        let output = match matches.value_of("output") {
+            Some(STDIO) => return Err("Can't write split modules to stdout".into()),
+            Some(output) => Path::new(output),
+            None => Path::new("types"),
+        };
+
+        fs::create_dir_all(output)?;
+        for module in modules {
+            let file = format!("{}.{}", module.name, extension);
+            fs::write(output.join(file), module.content)?;
+        }
+
+        return Ok(());
+    }
+
+    let output = match matches.value_of("output") {
+        Some(STDIO) => {
+            io::stdout().write_all(strategy.to_string_all(&typings, &settings).as_bytes())?;
+
+            return Ok(());
+        }
+        Some(output) => PathBuf::from(output),
+        None => PathBuf::from(format!("index.{}", strategy.extension(&settings))),
+    };
     if let Some(p) = output.parent() {
         fs::create_dir_all(p)?;
     };
 
-    let typings = typings
-        .iter()
-        .map(|t| t.as_string(Strategy::Tree))
-        .fold(String::new(), |acc, c| acc + &c);
-
-    fs::write(output, format!("{}\n", typings))?;
+    fs::write(output, strategy.to_string_all(&typings, &settings))?;
 
     Ok(())
 }
+
+/// Typing of every item of a file, json lines files and stdin are always streamed
+fn read_typing(path: &Path, name: &str, settings: &Settings) -> Result<Typing, Box<dyn Error>> {
+    if path.to_str() == Some(STDIO) {
+        let mut accumulator = TypingAccumulator::new(name, settings);
+        read_json::items(io::stdin().lock(), |item| accumulator.push(item))?;
+
+        Ok(accumulator.finish())
+    } else if read_json::is_json_lines(path) || settings.stream {
+        let mut accumulator = TypingAccumulator::new(name, settings);
+        read_json::stream(path, |item| accumulator.push(item))?;
+
+        Ok(accumulator.finish())
+    } else {
+        Ok(Typing::from_items_with_settings(
+            name,
+            read_json::file(path)?,
+            settings,
+        ))
+    }
+}
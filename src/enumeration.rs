@@ -0,0 +1,210 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+};
+
+use convert_case::{Case, Casing};
+use serde::{Deserialize, Serialize};
+
+use crate::{Literal, Settings};
+
+/// How sets of literals are declared in typescript outputs
+#[derive(Serialize, Deserialize, Debug, Hash, PartialEq, Eq, Clone)]
+pub enum LiteralStyle {
+    /// Union of the literals, `"a" | "b"`
+    Union,
+    /// `export enum`
+    Enum,
+    /// `export const enum`, inlined at compile time
+    ConstEnum,
+    /// `as const` object along with a type of its values
+    Object,
+}
+
+impl LiteralStyle {
+    /// Whether the style declares values, which a `.d.ts` file can't hold
+    pub fn declares_values(&self) -> bool {
+        matches!(self, Self::Enum | Self::Object)
+    }
+}
+
+/// Named set of literals, declared according to the `literal_style` setting
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct Enumeration {
+    pub name: String,
+    pub members: Vec<(String, Literal)>,
+}
+
+impl Enumeration {
+    /// Names every member after its value in PascalCase, members starting with a digit are
+    /// prefixed with `_` and colliding members get a numeric suffix
+    pub fn new<T: IntoIterator<Item = Literal>>(name: &str, literals: T) -> Self {
+        let mut seen: HashMap<String, usize> = HashMap::new();
+
+        let members = literals
+            .into_iter()
+            .map(|literal| {
+                let value = match &literal {
+                    Literal::String(s) | Literal::Template(s) => s.clone(),
+                    Literal::Number(n) => n.to_string(),
+                };
+                let mut member: String = value
+                    .to_case(Case::Pascal)
+                    .chars()
+                    .map(|c| match c.is_alphanumeric() || c == '_' || c == '$' {
+                        true => c,
+                        false => '_',
+                    })
+                    .collect();
+                if !member.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$') {
+                    member.insert(0, '_');
+                }
+
+                let count = seen.entry(member.clone()).or_insert(0);
+                *count += 1;
+
+                match count {
+                    1 => (member, literal),
+                    _ => (format!("{}{}", member, count), literal),
+                }
+            })
+            .collect();
+
+        Self {
+            name: String::from(name),
+            members,
+        }
+    }
+}
+
+impl Enumeration {
+    pub fn to_string(&self, settings: &Settings) -> String {
+        let mut s = String::new();
+        self.write(&mut s, settings).unwrap();
+
+        s
+    }
+
+    pub(crate) fn write<W: Write>(&self, f: &mut W, settings: &Settings) -> fmt::Result {
+        let indentation = &settings.indentation;
+        let delimiter = &settings.string_delimiter;
+
+        let (header, separator, footer) = match settings.literal_style {
+            LiteralStyle::Union => {
+                let values: Vec<_> = self
+                    .members
+                    .iter()
+                    .map(|(_, literal)| literal.to_string(delimiter))
+                    .collect();
+
+                return write!(f, "export type {} = {};", self.name, values.join(" | "));
+            }
+            LiteralStyle::Enum => (format!("export enum {} {{", self.name), " = ", "}"),
+            LiteralStyle::ConstEnum => (format!("export const enum {} {{", self.name), " = ", "}"),
+            LiteralStyle::Object => (
+                format!("export const {} = {{", self.name),
+                ": ",
+                "} as const;",
+            ),
+        };
+
+        writeln!(f, "{}", header)?;
+        for (member, literal) in &self.members {
+            writeln!(
+                f,
+                "{}{}{}{},",
+                indentation,
+                member,
+                separator,
+                literal.to_string(delimiter)
+            )?;
+        }
+        write!(f, "{}", footer)?;
+
+        if settings.literal_style == LiteralStyle::Object {
+            write!(
+                f,
+                "\nexport type {0} = typeof {0}[keyof typeof {0}];",
+                self.name
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::Number;
+
+    use super::*;
+
+    #[test]
+    fn members() {
+        let enumeration = Enumeration::new(
+            "Status",
+            vec![
+                Literal::from_str("in-progress"),
+                Literal::from_str("done"),
+                Literal::from_str("Done"),
+                Literal::Number(Number::from(404)),
+            ],
+        );
+        let members: Vec<_> = enumeration
+            .members
+            .iter()
+            .map(|(m, _)| m.as_str())
+            .collect();
+
+        self::assert_eq!(vec!["InProgress", "Done", "Done2", "_404"], members);
+    }
+
+    #[test]
+    fn styles() {
+        let enumeration = Enumeration::new(
+            "Status",
+            vec![Literal::from_str("on"), Literal::from_str("off")],
+        );
+        let style = |literal_style| Settings {
+            literal_style,
+            ..Settings::default()
+        };
+
+        self::assert_eq!(
+            "export type Status = \"on\" | \"off\";",
+            enumeration.to_string(&style(LiteralStyle::Union))
+        );
+        self::assert_eq!(
+            [
+                "export enum Status {",
+                "    On = \"on\",",
+                "    Off = \"off\",",
+                "}"
+            ]
+            .join("\n"),
+            enumeration.to_string(&style(LiteralStyle::Enum))
+        );
+        self::assert_eq!(
+            [
+                "export const enum Status {",
+                "    On = \"on\",",
+                "    Off = \"off\",",
+                "}"
+            ]
+            .join("\n"),
+            enumeration.to_string(&style(LiteralStyle::ConstEnum))
+        );
+        self::assert_eq!(
+            [
+                "export const Status = {",
+                "    On: \"on\",",
+                "    Off: \"off\",",
+                "} as const;",
+                "export type Status = typeof Status[keyof typeof Status];",
+            ]
+            .join("\n"),
+            enumeration.to_string(&style(LiteralStyle::Object))
+        );
+    }
+}
@@ -1,9 +1,25 @@
-use std::fmt::Display;
+use std::fmt::{self, Write};
 
 use convert_case::{Case, Casing};
 use itertools::{Either, Itertools};
+use serde::{Deserialize, Serialize};
 
-use super::SETTINGS;
+use crate::{utils::property_key, Settings};
+
+/// How keys that are missing or `null` in some objects are typed in typescript outputs
+#[derive(Serialize, Deserialize, Debug, Hash, PartialEq, Eq, Clone)]
+pub enum NullStyle {
+    /// `key?: T` when missing and `key: T | null` when null
+    Separate,
+    /// `key?: T`, null values are dropped
+    Optional,
+    /// `key: T | null`
+    Null,
+    /// `key?: T | null`
+    OptionalNull,
+    /// `key: T | undefined`
+    Undefined,
+}
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 pub struct Interface {
@@ -17,6 +33,8 @@ pub struct InterfaceEntry {
     pub key: String,
     pub value: String,
     pub optional: bool,
+    /// Documentation comment written above the entry
+    pub comment: Option<String>,
 }
 
 impl Interface {
@@ -29,22 +47,54 @@ impl Interface {
     }
 }
 
-impl Display for Interface {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let indentation = &SETTINGS.read().indentation;
-        let to_sort = SETTINGS.read().sort;
+impl NullStyle {
+    /// Type and optionality of an entry from its type without `null`, empty when the key was
+    /// only ever null, whether the key is missing from some objects and whether it is null in
+    /// some of them
+    pub fn apply(&self, value: &str, optional: bool, nullable: bool) -> (String, bool) {
+        let absent = optional || nullable;
+        let (null, undefined, optional) = match self {
+            Self::Separate => (nullable, false, optional),
+            Self::Optional => (false, false, absent),
+            Self::Null => (absent, false, false),
+            Self::OptionalNull => (absent, false, absent),
+            Self::Undefined => (false, absent, false),
+        };
+
+        let mut members = vec![];
+        if !value.is_empty() {
+            members.push(value);
+        }
+        if null || members.is_empty() {
+            members.push("null");
+        }
+        if undefined {
+            members.push("undefined");
+        }
+
+        (members.join(" | "), optional)
+    }
+}
+
+impl Interface {
+    pub fn to_string(&self, settings: &Settings) -> String {
+        let mut s = String::new();
+        self.write(&mut s, settings).unwrap();
+
+        s
+    }
+
+    pub(crate) fn write<W: Write>(&self, f: &mut W, settings: &Settings) -> fmt::Result {
+        let indentation = &settings.indentation;
+        let to_sort = settings.sort;
 
         if let Some(interface) = &self.extends {
-            write!(
-                f,
-                "export interface {} extends {} {{\n",
-                self.name, interface
-            )?;
+            writeln!(f, "export interface {} extends {} {{", self.name, interface)?;
         } else {
-            write!(f, "export interface {} {{\n", self.name)?;
+            writeln!(f, "export interface {} {{", self.name)?;
         };
 
-        let iter = if to_sort {
+        let mut iter = if to_sort {
             Either::Left(
                 self.entries
                     .iter()
@@ -53,15 +103,63 @@ impl Display for Interface {
         } else {
             Either::Right(self.entries.iter())
         };
-        iter.map(|e| {
+        iter.try_for_each(|e| {
+            if let Some(comment) = &e.comment {
+                writeln!(f, "{}/** {} */", indentation, comment)?;
+            }
+
+            let readonly = if settings.readonly { "readonly " } else { "" };
+            let key = property_key(&e.key, &settings.string_delimiter);
             if e.optional {
-                write!(f, "{}{}?: {};\n", indentation, e.key, e.value)
+                writeln!(f, "{}{}{}?: {};", indentation, readonly, key, e.value)
             } else {
-                write!(f, "{}{}: {};\n", indentation, e.key, e.value)
+                writeln!(f, "{}{}{}: {};", indentation, readonly, key, e.value)
             }
-        })
-        .collect::<std::fmt::Result>()?;
+        })?;
 
         write!(f, "}}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn null_styles() {
+        let cases = [
+            (NullStyle::Separate, ("T | null", false), ("T", true)),
+            (NullStyle::Optional, ("T", true), ("T", true)),
+            (NullStyle::Null, ("T | null", false), ("T | null", false)),
+            (
+                NullStyle::OptionalNull,
+                ("T | null", true),
+                ("T | null", true),
+            ),
+            (
+                NullStyle::Undefined,
+                ("T | undefined", false),
+                ("T | undefined", false),
+            ),
+        ];
+
+        for (style, null, missing) in cases {
+            let to_owned = |(value, optional): (&str, bool)| (String::from(value), optional);
+
+            self::assert_eq!(to_owned(null), style.apply("T", false, true), "{:?}", style);
+            self::assert_eq!(
+                to_owned(missing),
+                style.apply("T", true, false),
+                "{:?}",
+                style
+            );
+            self::assert_eq!(("T".to_string(), false), style.apply("T", false, false));
+        }
+
+        self::assert_eq!(
+            (String::from("null"), true),
+            NullStyle::Optional.apply("", false, true)
+        );
+    }
+}
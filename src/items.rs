@@ -1,6 +1,14 @@
-use ijson::{Destructured, IArray, IValue};
+use std::collections::{HashMap, HashSet};
+
+use ijson::{Destructured, IArray, IObject, IString, IValue};
 use itertools::Itertools;
 
+use crate::regex;
+
+/// Fewest generated keys for an object to be typed as a record below the threshold, so that
+/// short keys such as `x1` or `h2` keep their shape
+const MIN_GENERATED_KEYS: usize = 4;
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct Items(IArray);
 
@@ -11,15 +19,159 @@ pub struct ItemEntry {
     pub optional: bool,
 }
 
+impl Default for Items {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Items {
     pub fn new() -> Self {
         Self(IArray::new())
     }
 
+    /// Whether these objects look like a map with dynamic keys instead of a fixed shape
+    ///
+    /// Every value must have the same type (and objects roughly the same keys), and either there
+    /// are at least `MIN_GENERATED_KEYS` keys looking generated (ids, hashes, dates...) and made
+    /// mostly of digits, or at least `threshold` keys varying between the objects. A `threshold`
+    /// of 0 only keeps generated keys.
+    pub fn is_record(&self, threshold: usize) -> bool {
+        let objects: Vec<_> = self.0.iter().filter_map(|v| v.as_object()).collect();
+        let keys: HashSet<_> = objects.iter().flat_map(|o| o.keys()).collect();
+        if keys.len() < 2 {
+            return false;
+        }
+
+        let values: Vec<_> = objects.iter().flat_map(|o| o.values()).collect();
+        if !values.iter().map(|v| v.type_()).all_equal() {
+            return false;
+        }
+
+        let value_objects: Vec<_> = values.iter().filter_map(|v| v.as_object()).collect();
+        if !value_objects.is_empty() {
+            let value_keys: HashSet<_> = value_objects.iter().flat_map(|o| o.keys()).collect();
+            let common_keys = value_keys
+                .iter()
+                .filter(|k| value_objects.iter().all(|o| o.contains_key(**k)))
+                .count();
+
+            if common_keys * 2 < value_keys.len() {
+                return false;
+            }
+        }
+
+        let digits = keys
+            .iter()
+            .flat_map(|k| k.chars())
+            .filter(char::is_ascii_digit)
+            .count();
+        let letters = keys
+            .iter()
+            .flat_map(|k| k.chars())
+            .filter(char::is_ascii_alphabetic)
+            .count();
+        let dynamic_keys = keys.len() >= MIN_GENERATED_KEYS
+            && digits > letters
+            && keys
+                .iter()
+                .all(|k| regex::DYNAMIC_KEY.is_match(k).unwrap_or(false));
+        let average_len = objects.iter().map(|o| o.len()).sum::<usize>() / objects.len();
+        let varying_keys = average_len * 2 <= keys.len();
+
+        dynamic_keys || (threshold > 0 && keys.len() >= threshold && varying_keys)
+    }
+
+    /// Key whose string values tell apart objects of different shapes, such as the `type` of
+    /// events, `None` when splitting the objects by any key doesn't make fewer keys optional
+    ///
+    /// The key must be in every object with a string no longer than `max_length`, and take
+    /// between 2 and `max_values` distinct values with at least one of them repeated.
+    pub fn discriminator(&self, max_values: usize, max_length: usize) -> Option<String> {
+        let objects: Vec<_> = self.0.iter().filter_map(|v| v.as_object()).collect();
+        let first = objects.first()?;
+
+        let optional_keys = |objects: &[&IObject]| -> usize {
+            let keys: HashSet<_> = objects.iter().flat_map(|o| o.keys()).collect();
+            keys.iter()
+                .filter(|k| !objects.iter().all(|o| o.contains_key(**k)))
+                .count()
+        };
+        let optional = optional_keys(&objects);
+
+        first
+            .keys()
+            .filter_map(|key| {
+                let values: Vec<_> = objects
+                    .iter()
+                    .map(|o| o.get(key).and_then(|v| v.as_string()))
+                    .collect::<Option<_>>()?;
+                let uniques = values.iter().unique().count();
+
+                let literal = values
+                    .iter()
+                    .all(|v| !v.is_empty() && v.len() <= max_length);
+                if !literal || uniques < 2 || uniques > max_values || uniques == values.len() {
+                    return None;
+                }
+
+                let groups = objects
+                    .iter()
+                    .zip(&values)
+                    .into_group_map_by(|(_, v)| **v)
+                    .into_values()
+                    .map(|group| group.into_iter().map(|(o, _)| *o).collect_vec())
+                    .map(|group| optional_keys(&group))
+                    .sum::<usize>();
+
+                Some((groups, key))
+            })
+            .filter(|(groups, _)| *groups < optional)
+            .min_by_key(|(groups, _)| *groups)
+            .map(|(_, key)| String::from(key.as_str()))
+    }
+
+    /// Objects grouped by the value of `key`, in the order the values first appear
+    pub fn variants(self, key: &str) -> Vec<(String, Items)> {
+        let mut variants: Vec<(String, Vec<IValue>)> = vec![];
+        for value in self.0 {
+            let variant = value
+                .as_object()
+                .and_then(|o| o.get(key))
+                .and_then(|v| v.as_string())
+                .map(|s| String::from(s.as_str()))
+                .unwrap_or_default();
+
+            match variants.iter_mut().find(|(v, _)| *v == variant) {
+                Some((_, values)) => values.push(value),
+                None => variants.push((variant, vec![value])),
+            }
+        }
+
+        variants
+            .into_iter()
+            .map(|(variant, values)| (variant, values.into()))
+            .collect()
+    }
+
+    /// Values of every key of every object
+    pub fn values(self) -> Vec<IValue> {
+        self.0
+            .into_iter()
+            .filter_map(|v| match v.destructure() {
+                Destructured::Object(o) => Some(o.into_iter().map(|(_, v)| v)),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
     pub fn entries(self) -> impl Iterator<Item = ItemEntry> {
         let values = self.0;
         let values_len = values.len();
 
+        let mut key_index: HashMap<IString, usize> = HashMap::new();
+        let mut groups: Vec<(IString, Vec<IValue>)> = vec![];
         values
             .into_iter()
             .filter_map(|v| match v.destructure() {
@@ -27,18 +179,22 @@ impl Items {
                 _ => None,
             })
             .flatten()
-            .into_group_map_by(|(k, _)| k.clone())
-            .into_iter()
-            .map(move |(key, group)| {
-                let sub_values: Vec<_> = group.into_iter().map(|(_, v)| v).collect();
-
-                let optional = sub_values.len() < values_len;
-                ItemEntry {
-                    optional,
-                    key: key.clone().into(),
-                    values: sub_values,
+            .for_each(|(k, v)| match key_index.get(&k) {
+                Some(&i) => groups[i].1.push(v),
+                None => {
+                    key_index.insert(k.clone(), groups.len());
+                    groups.push((k, vec![v]));
                 }
-            })
+            });
+
+        groups.into_iter().map(move |(key, sub_values)| {
+            let optional = sub_values.len() < values_len;
+            ItemEntry {
+                optional,
+                key: key.into(),
+                values: sub_values,
+            }
+        })
     }
 }
 
@@ -109,4 +265,94 @@ mod tests {
 
         self::assert_eq!(entries, expected)
     }
+
+    #[test]
+    pub fn record_dynamic_keys() {
+        let values = ijson!({
+            "u123": { "name": "a", "age": 1 },
+            "u456": { "name": "b" },
+            "u789": { "name": "c" },
+            "u1011": { "name": "d" },
+        });
+
+        assert!(Items::from(values.clone()).is_record(8));
+        assert!(Items::from(values).is_record(0));
+
+        let values = ijson!({ "u123": { "name": "a" }, "u456": { "name": "b" } });
+
+        assert!(!Items::from(values).is_record(8));
+    }
+
+    #[test]
+    pub fn record_coordinate_keys() {
+        let values = ijson!({ "x1": 0, "y1": 0, "x2": 10, "y2": 10 });
+
+        assert!(!Items::from(values.clone()).is_record(8));
+        assert!(!Items::from(values).is_record(0));
+
+        let values = ijson!([{ "h1": "a", "h2": "b", "h3": "c", "h4": "d", "h5": "e" }]);
+
+        assert!(!Items::from(values).is_record(8));
+    }
+
+    #[test]
+    pub fn record_varying_keys() {
+        let values = ijson!([
+            { "en": "Hello", "fr": "Bonjour" },
+            { "de": "Hallo", "es": "Hola" },
+        ]);
+
+        assert!(Items::from(values.clone()).is_record(4));
+        assert!(!Items::from(values.clone()).is_record(5));
+        assert!(!Items::from(values).is_record(0));
+    }
+
+    #[test]
+    pub fn record_mixed_values() {
+        let values = ijson!({ "1": "a", "2": 2 });
+
+        assert!(!Items::from(values.clone()).is_record(8));
+        assert!(!Items::from(values).is_record(0));
+    }
+
+    #[test]
+    pub fn not_record() {
+        let values = ijson!({ "title": "a", "name": "b" });
+
+        assert!(!Items::from(values).is_record(8));
+    }
+
+    #[test]
+    pub fn discriminator() {
+        let values = ijson!([
+            { "kind": "circle", "name": "a", "radius": 1 },
+            { "kind": "square", "name": "b", "side": 2 },
+            { "kind": "circle", "name": "c", "radius": 3 },
+        ]);
+        let items = Items::from(values);
+
+        self::assert_eq!(Some(String::from("kind")), items.discriminator(32, 16));
+        self::assert_eq!(None, items.discriminator(1, 16));
+
+        let variants: Vec<_> = items
+            .variants("kind")
+            .into_iter()
+            .map(|(variant, items)| (variant, items.0.len()))
+            .collect();
+        self::assert_eq!(
+            vec![(String::from("circle"), 2), (String::from("square"), 1)],
+            variants
+        );
+    }
+
+    #[test]
+    pub fn no_discriminator() {
+        let values = ijson!([
+            { "kind": "circle", "radius": 1 },
+            { "kind": "square", "radius": 2 },
+            { "kind": "circle", "radius": 3 },
+        ]);
+
+        self::assert_eq!(None, Items::from(values).discriminator(32, 16));
+    }
 }
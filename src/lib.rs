@@ -6,19 +6,30 @@ pub mod strategy;
 mod settings;
 mod literal;
 mod typing;
+mod merge;
+mod accumulator;
 mod items;
 mod path;
 mod nesting_level;
 mod interface;
 mod namespace;
+mod enumeration;
+mod string_format;
+mod number_range;
 mod union;
+mod array;
 
 pub use path::TypePath;
 pub use settings::{Settings, SETTINGS};
 pub use items::{Items, ItemEntry};
 pub use typing::{Typing, TypingNode, TypingGraph};
+pub use accumulator::TypingAccumulator;
 pub use literal::Literal;
 pub use nesting_level::NestingLevel;
 pub use namespace::{Namespace, NamespaceEntry, NamespaceEntryValue};
-pub use interface::{Interface, InterfaceEntry};
+pub use interface::{Interface, InterfaceEntry, NullStyle};
+pub use enumeration::{Enumeration, LiteralStyle};
+pub use string_format::{FormatStyle, StringFormat};
+pub use number_range::{BigIntStyle, NumberKind, NumberRange};
 pub use union::TypingUnion;
+pub use array::ArraySyntax;
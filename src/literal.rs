@@ -1,14 +1,12 @@
 use std::{
-    fmt::{self, Display},
     hash::{Hash, Hasher},
     mem::discriminant,
 };
 
+use ijson::INumber;
 use ordered_float::OrderedFloat;
 use serde_json::Number;
 
-use crate::SETTINGS;
-
 #[allow(dead_code)]
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Literal {
@@ -18,18 +16,27 @@ pub enum Literal {
 }
 
 impl Literal {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         Self::String(String::from(s))
     }
-}
 
-impl Display for Literal {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let sep = &SETTINGS.try_read().ok_or(fmt::Error)?.string_delimiter;
+    pub fn from_number(n: &INumber) -> Self {
+        let number = match (n.to_i64(), n.to_u64()) {
+            (Some(i), _) => Number::from(i),
+            (_, Some(u)) => Number::from(u),
+            _ => Number::from_f64(n.to_f64_lossy()).unwrap_or_else(|| Number::from(0)),
+        };
+
+        Self::Number(number)
+    }
+
+    /// Typescript literal type, strings are surrounded by `delimiter`
+    pub fn to_string(&self, delimiter: &str) -> String {
         match self {
-            Self::Number(n) => n.fmt(f),
-            Self::Template(s) => write!(f, "`{}`", s),
-            Self::String(s) => write!(f, "{0}{1}{0}", sep, s),
+            Self::Number(n) => n.to_string(),
+            Self::Template(s) => format!("`{}`", s),
+            Self::String(s) => format!("{0}{1}{0}", delimiter, s),
         }
     }
 }
@@ -56,31 +63,34 @@ mod tests {
 
     #[test]
     fn display_integer() {
-        assert_eq!("1024", format!("{}", Literal::Number(Number::from(1024))));
-        assert_eq!("-1024", format!("{}", Literal::Number(Number::from(-1024))));
+        assert_eq!("1024", Literal::Number(Number::from(1024)).to_string("\""));
+        assert_eq!(
+            "-1024",
+            Literal::Number(Number::from(-1024)).to_string("\"")
+        );
 
-        assert_eq!("0", format!("{}", Literal::Number(Number::from(0))));
-        assert_eq!("0", format!("{}", Literal::Number(Number::from(-0))));
+        assert_eq!("0", Literal::Number(Number::from(0)).to_string("\""));
+        assert_eq!("0", Literal::Number(Number::from(-0)).to_string("\""));
     }
 
     #[test]
     fn display_float() {
         assert_eq!(
             "7.65",
-            format!("{}", Literal::Number(Number::from_f64(7.65).unwrap()))
+            Literal::Number(Number::from_f64(7.65).unwrap()).to_string("\"")
         );
         assert_eq!(
             "-7.65",
-            format!("{}", Literal::Number(Number::from_f64(-7.65).unwrap()))
+            Literal::Number(Number::from_f64(-7.65).unwrap()).to_string("\"")
         );
 
         assert_eq!(
             "0",
-            format!("{}", Literal::Number(Number::from_f64(0.0).unwrap()))
+            Literal::Number(Number::from_f64(0.0).unwrap()).to_string("\"")
         );
         assert_eq!(
             "-0",
-            format!("{}", Literal::Number(Number::from_f64(-0.0).unwrap()))
+            Literal::Number(Number::from_f64(-0.0).unwrap()).to_string("\"")
         );
     }
 
@@ -88,7 +98,11 @@ mod tests {
     fn display_string() {
         assert_eq!(
             "\"test\"",
-            format!("{}", Literal::String(String::from("test")))
+            Literal::String(String::from("test")).to_string("\"")
+        );
+        assert_eq!(
+            "'test'",
+            Literal::String(String::from("test")).to_string("'")
         );
     }
 }
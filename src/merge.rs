@@ -0,0 +1,731 @@
+use std::{collections::HashMap, mem::discriminant};
+
+use ijson::INumber;
+use itertools::Itertools;
+use petgraph::{
+    graph::NodeIndex,
+    visit::{Dfs, EdgeRef},
+    Direction,
+};
+
+use crate::{typing::variant_names, Literal, NumberRange, Settings, Typing, TypingNode};
+
+impl Typing {
+    /// Unifies `other` into this typing as if both had been inferred from the same items.
+    /// Keys missing from one side become optional and the types of every value are unioned.
+    ///
+    /// Merging should happen before deduplicating since merging into a shared object affects
+    /// every place it is referenced from.
+    pub fn merge(&mut self, other: &Typing) {
+        self.merge_with_settings(other, &Settings::default())
+    }
+
+    /// Unifies `other` into this typing, sets of literals growing past the thresholds of
+    /// `settings` collapse into `string` or `number` as if every item had been inferred at once
+    pub fn merge_with_settings(&mut self, other: &Typing, settings: &Settings) {
+        self.merge_chunk(other, settings);
+        self.rebuild(|nx| nx);
+    }
+
+    /// Merges `other` like `merge_with_settings` without dropping the nodes no longer
+    /// reachable, so that chunks can be merged one after the other and rebuilt once at the end
+    pub(crate) fn merge_chunk(&mut self, other: &Typing, settings: &Settings) {
+        let root = NodeIndex::new(0);
+        let mut copies = HashMap::new();
+
+        // A root that is a union on either side is merged as a union of its variants
+        match (self.is_union(root), other.is_union(root)) {
+            (false, false) => self.merge_object(root, other, root, &mut copies),
+            (union, o_union) => {
+                if !union {
+                    self.nest_root();
+                }
+
+                match o_union {
+                    true => self.merge_values(root, other, root, root, &mut copies),
+                    false => {
+                        let child = self.copy(other, root, root, &mut copies);
+                        self.graph.add_edge(root, child, ());
+                    }
+                }
+            }
+        }
+
+        let mut reachable = vec![];
+        let mut dfs = Dfs::new(&self.graph, root);
+        while let Some(nx) = dfs.next(&self.graph) {
+            reachable.push(nx);
+        }
+        if settings.discriminated_unions {
+            for &nx in &reachable {
+                self.fold_variants(nx);
+            }
+        }
+        for &nx in &reachable {
+            self.collapse_literals(nx, settings);
+        }
+    }
+
+    /// Moves the entries of the root into an object of its own under the root, making the
+    /// root a union with a single variant
+    fn nest_root(&mut self) {
+        let root = NodeIndex::new(0);
+        let entries: Vec<_> = self.children(root).collect();
+
+        let edges: Vec<_> = self.graph.edges(root).map(|edge| edge.id()).collect();
+        // Removing an edge moves the last one in its place, remove the last first
+        for edge in edges.into_iter().sorted().rev() {
+            self.graph.remove_edge(edge);
+        }
+
+        let child = self.graph.add_node(self.graph[root].clone());
+        self.graph.add_edge(root, child, ());
+        for entry in entries {
+            self.graph.add_edge(child, entry, ());
+        }
+        self.set_object_node(child, root, child);
+    }
+
+    /// Hands the nodes under `nx` owned by the object `from` over to the object `to`, the
+    /// nodes under nested objects keep their owner
+    fn set_object_node(&mut self, nx: NodeIndex, from: NodeIndex, to: NodeIndex) {
+        for n_nx in self.children(nx).collect_vec() {
+            match &mut self.graph[n_nx] {
+                TypingNode::Object(_) => continue,
+                TypingNode::Array { object_node, .. }
+                | TypingNode::Record { object_node, .. }
+                | TypingNode::Tuple { object_node, .. }
+                | TypingNode::TupleElement { object_node, .. }
+                | TypingNode::ObjectEntry { object_node, .. }
+                    if *object_node == from =>
+                {
+                    *object_node = to
+                }
+                _ => {}
+            }
+
+            self.set_object_node(n_nx, from, to);
+        }
+    }
+
+    /// Typing holding a copy of the object `nx` and everything under it, `nx` being its root
+    fn extract(&self, nx: NodeIndex) -> Typing {
+        let mut typing = Typing::new(&self.name);
+        let root = NodeIndex::new(0);
+        typing.copy(self, nx, root, &mut HashMap::new());
+
+        typing
+    }
+
+    /// Folds the objects under `nx` sharing the value of the discriminator of the variants
+    /// found there into a single variant, such as an object inferred from items with a single
+    /// value of the discriminator next to the variants inferred from other items. Every
+    /// variant is then named after its value.
+    fn fold_variants(&mut self, nx: NodeIndex) {
+        let objects: Vec<_> = self
+            .children(nx)
+            .filter(|n_nx| self.graph[*n_nx].is_object())
+            .collect();
+        if objects.len() < 2 {
+            return;
+        }
+
+        let keys: Vec<_> = objects
+            .iter()
+            .flat_map(|o_nx| self.children(*o_nx))
+            .filter_map(|e_nx| self.graph[e_nx].as_object_entry())
+            .map(|(key, _)| String::from(key))
+            .unique()
+            .collect();
+        let discriminator = keys.into_iter().find_map(|key| {
+            let values: Vec<_> = objects
+                .iter()
+                .filter_map(|o_nx| self.discriminator_value(*o_nx, &key).map(|v| (*o_nx, v)))
+                .collect();
+
+            match values.iter().map(|(_, v)| v).unique().count() {
+                0 | 1 => None,
+                _ => Some(values),
+            }
+        });
+        let values = match discriminator {
+            Some(values) => values,
+            None => return,
+        };
+
+        let groups = values.into_iter().into_group_map_by(|(_, v)| v.clone());
+        let variants: Vec<_> = objects
+            .iter()
+            .filter_map(|o_nx| groups.iter().find(|(_, group)| group[0].0 == *o_nx))
+            .collect();
+
+        let prefix = match &self.graph[nx] {
+            TypingNode::ObjectEntry { key, .. }
+            | TypingNode::Array { key, .. }
+            | TypingNode::Record { key, .. } => key.clone(),
+            TypingNode::TupleElement { index, .. } => {
+                let tuple = self
+                    .graph
+                    .neighbors_directed(nx, Direction::Incoming)
+                    .next()
+                    .unwrap();
+                let key = match &self.graph[tuple] {
+                    TypingNode::Tuple { key, .. } => key.as_str(),
+                    _ => "",
+                };
+
+                format!("{}_element_{}", key, index)
+            }
+            _ => String::new(),
+        };
+        let names = variant_names(&prefix, variants.iter().map(|(value, _)| value.as_str()));
+
+        for ((_, group), name) in variants.into_iter().zip(names) {
+            let variant = group[0].0;
+            for &(o_nx, _) in &group[1..] {
+                // Only the folded object is copied, not the whole typing
+                let other = self.extract(o_nx);
+                self.merge_object(variant, &other, NodeIndex::new(0), &mut HashMap::new());
+
+                let edge = self.graph.find_edge(nx, o_nx).unwrap();
+                self.graph.remove_edge(edge);
+            }
+
+            self.graph[variant] = TypingNode::Object(name);
+        }
+    }
+
+    /// Value of the `key` entry of the object `nx` when it is a single string literal
+    fn discriminator_value(&self, nx: NodeIndex, key: &str) -> Option<String> {
+        let entry = self
+            .children(nx)
+            .find(|e_nx| self.graph[*e_nx].as_object_entry().map(|(k, _)| k) == Some(key))?;
+
+        match self.children(entry).collect_vec().as_slice() {
+            [literal] => match &self.graph[*literal] {
+                TypingNode::Literal(Literal::String(value)) => Some(value.clone()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Replaces the literals and formats of `nx` by `string` or `number` when they no longer
+    /// hold for every value: too many or too long literals, or formats mixed with each other
+    /// or with literals
+    fn collapse_literals(&mut self, nx: NodeIndex, settings: &Settings) {
+        let children: Vec<_> = self.children(nx).collect();
+        let strings: Vec<_> = children
+            .iter()
+            .copied()
+            .filter(|n_nx| {
+                matches!(
+                    self.graph[*n_nx],
+                    TypingNode::Literal(Literal::String(_)) | TypingNode::Format(_)
+                )
+            })
+            .collect();
+        let numbers: Vec<_> = children
+            .iter()
+            .filter_map(|n_nx| match &self.graph[*n_nx] {
+                TypingNode::Literal(Literal::Number(n)) => Some((*n_nx, n)),
+                _ => None,
+            })
+            .collect();
+
+        let formats = strings
+            .iter()
+            .filter(|n_nx| matches!(self.graph[**n_nx], TypingNode::Format(_)))
+            .count();
+        let too_long = strings.iter().any(|n_nx| match &self.graph[*n_nx] {
+            TypingNode::Literal(Literal::String(s)) => s.len() > settings.literal_max_length,
+            _ => false,
+        });
+
+        let number_range = match numbers.len() > settings.number_literal_max_values {
+            // Number literals are always integers
+            true => NumberRange::from_numbers(
+                &numbers
+                    .iter()
+                    .filter_map(|(_, n)| match (n.as_i64(), n.as_u64()) {
+                        (Some(i), _) => Some(INumber::from(i)),
+                        (_, Some(u)) => Some(INumber::from(u)),
+                        _ => None,
+                    })
+                    .collect_vec(),
+            ),
+            false => None,
+        };
+        let numbers: Vec<_> = numbers.into_iter().map(|(n_nx, _)| n_nx).collect();
+
+        if (formats > 0 && strings.len() > 1)
+            || (formats == 0 && strings.len() > settings.max_literal_values())
+            || too_long
+        {
+            self.replace_children(nx, &strings, TypingNode::String);
+        }
+        if let Some(range) = number_range {
+            self.replace_children(nx, &numbers, TypingNode::Number(range));
+        }
+    }
+
+    /// Removes the edges from `nx` to `children` and adds a child holding `node` in their place
+    fn replace_children(&mut self, nx: NodeIndex, children: &[NodeIndex], node: TypingNode) {
+        let edges: Vec<_> = self
+            .graph
+            .edges(nx)
+            .filter(|edge| children.contains(&edge.target()))
+            .map(|edge| edge.id())
+            .collect();
+        // Removing an edge moves the last one in its place, remove the last first
+        for edge in edges.into_iter().sorted().rev() {
+            self.graph.remove_edge(edge);
+        }
+
+        let child = self.graph.add_node(node);
+        self.graph.add_edge(nx, child, ());
+    }
+
+    fn merge_object(
+        &mut self,
+        nx: NodeIndex,
+        other: &Typing,
+        o_nx: NodeIndex,
+        copies: &mut HashMap<NodeIndex, NodeIndex>,
+    ) {
+        let entries: Vec<_> = self.children(nx).collect();
+        let mut merged = vec![];
+        for o_entry in other.children(o_nx) {
+            let (key, o_optional) = other.graph[o_entry].as_object_entry().unwrap();
+            let entry = entries
+                .iter()
+                .find(|n_nx| self.graph[**n_nx].as_object_entry().unwrap().0 == key);
+
+            match entry {
+                Some(&entry) => {
+                    if o_optional {
+                        set_optional(&mut self.graph[entry]);
+                    }
+                    self.merge_values(entry, other, o_entry, nx, copies);
+                    merged.push(entry);
+                }
+                None => {
+                    let entry = self.copy(other, o_entry, nx, copies);
+                    set_optional(&mut self.graph[entry]);
+                    self.graph.add_edge(nx, entry, ());
+                }
+            }
+        }
+
+        for entry in entries.into_iter().filter(|n_nx| !merged.contains(n_nx)) {
+            set_optional(&mut self.graph[entry]);
+        }
+    }
+
+    /// Unions the children of `o_nx` into the children of `nx`, nested arrays, records,
+    /// tuples and objects of the same kind are merged instead of being added side by side
+    fn merge_values(
+        &mut self,
+        nx: NodeIndex,
+        other: &Typing,
+        o_nx: NodeIndex,
+        object_node: NodeIndex,
+        copies: &mut HashMap<NodeIndex, NodeIndex>,
+    ) {
+        for o_child in other.children(o_nx) {
+            let o_node = &other.graph[o_child];
+            let child = self
+                .children(nx)
+                .find(|n_nx| same_kind(&self.graph[*n_nx], o_node));
+
+            match (child, o_node) {
+                (Some(child), TypingNode::Array { .. })
+                | (Some(child), TypingNode::Record { .. }) => {
+                    self.merge_values(child, other, o_child, object_node, copies)
+                }
+                (Some(child), TypingNode::Tuple { .. }) => {
+                    self.merge_tuple(child, other, o_child, object_node, copies)
+                }
+                (Some(child), TypingNode::Object(_)) => {
+                    self.merge_object(child, other, o_child, copies)
+                }
+                (Some(child), TypingNode::Number(o_range)) => {
+                    if let TypingNode::Number(range) = &mut self.graph[child] {
+                        *range = range.union(o_range);
+                    }
+                }
+                (Some(_), _) => {}
+                (None, TypingNode::Literal(_)) | (None, TypingNode::Format(_)) => {
+                    let subsumed = self
+                        .children(nx)
+                        .any(|n_nx| subsumes(&self.graph[n_nx], o_node));
+
+                    if !subsumed {
+                        let child = self.copy(other, o_child, object_node, copies);
+                        self.graph.add_edge(nx, child, ());
+                    }
+                }
+                (None, TypingNode::String) | (None, TypingNode::Number(_)) => {
+                    // Any string or number subsumes the literals and formats of its type seen so far
+                    let literals: Vec<_> = self
+                        .graph
+                        .edges(nx)
+                        .filter(|edge| subsumes(o_node, &self.graph[edge.target()]))
+                        .map(|edge| edge.id())
+                        .collect();
+                    // Removing an edge moves the last one in its place, remove the last first
+                    for edge in literals.into_iter().sorted().rev() {
+                        self.graph.remove_edge(edge);
+                    }
+
+                    let child = self.copy(other, o_child, object_node, copies);
+                    self.graph.add_edge(nx, child, ());
+                }
+                (None, _) => {
+                    let child = self.copy(other, o_child, object_node, copies);
+                    self.graph.add_edge(nx, child, ());
+                }
+            }
+        }
+    }
+
+    /// Merges tuples position by position, positions missing from one side become optional
+    fn merge_tuple(
+        &mut self,
+        nx: NodeIndex,
+        other: &Typing,
+        o_nx: NodeIndex,
+        object_node: NodeIndex,
+        copies: &mut HashMap<NodeIndex, NodeIndex>,
+    ) {
+        let elements: Vec<_> = self.children(nx).collect();
+        let o_elements: Vec<_> = other.children(o_nx).collect();
+
+        for (index, o_element) in o_elements.iter().enumerate() {
+            let (_, o_optional) = other.graph[*o_element].as_tuple_element().unwrap();
+
+            match elements.get(index) {
+                Some(&element) => {
+                    if o_optional {
+                        set_optional(&mut self.graph[element]);
+                    }
+                    self.merge_values(element, other, *o_element, object_node, copies);
+                }
+                None => {
+                    let element = self.copy(other, *o_element, object_node, copies);
+                    set_optional(&mut self.graph[element]);
+                    self.graph.add_edge(nx, element, ());
+                }
+            }
+        }
+
+        for &element in elements.iter().skip(o_elements.len()) {
+            set_optional(&mut self.graph[element]);
+        }
+    }
+
+    /// Copies the subgraph of `other` starting at `o_nx` into this typing, nodes that are not
+    /// objects are owned by `object_node`
+    fn copy(
+        &mut self,
+        other: &Typing,
+        o_nx: NodeIndex,
+        object_node: NodeIndex,
+        copies: &mut HashMap<NodeIndex, NodeIndex>,
+    ) -> NodeIndex {
+        if let Some(nx) = copies.get(&o_nx) {
+            return *nx;
+        }
+
+        let node = match other.graph[o_nx].clone() {
+            TypingNode::Array { key, .. } => TypingNode::Array { object_node, key },
+            TypingNode::Record { key, .. } => TypingNode::Record { object_node, key },
+            TypingNode::Tuple { key, .. } => TypingNode::Tuple { object_node, key },
+            TypingNode::TupleElement {
+                index, optional, ..
+            } => TypingNode::TupleElement {
+                index,
+                optional,
+                object_node,
+            },
+            TypingNode::ObjectEntry { key, optional, .. } => TypingNode::ObjectEntry {
+                key,
+                optional,
+                object_node,
+            },
+            node => node,
+        };
+        let nx = self.graph.add_node(node);
+
+        let object_node = match self.graph[nx] {
+            TypingNode::Object(_) => {
+                copies.insert(o_nx, nx);
+                nx
+            }
+            _ => object_node,
+        };
+        for o_child in other.children(o_nx) {
+            let child = self.copy(other, o_child, object_node, copies);
+            self.graph.add_edge(nx, child, ());
+        }
+
+        nx
+    }
+}
+
+/// Whether both nodes hold the same kind of value, literals are only the same kind when equal
+/// and objects when they have the same name, telling apart the variants of a union
+fn same_kind(a: &TypingNode, b: &TypingNode) -> bool {
+    match (a, b) {
+        (TypingNode::Literal(a), TypingNode::Literal(b)) => a == b,
+        (TypingNode::Object(a), TypingNode::Object(b)) => a == b,
+        (TypingNode::Format(a), TypingNode::Format(b)) => a == b,
+        _ => discriminant(a) == discriminant(b),
+    }
+}
+
+/// Whether every value of the `literal` or format node is also a value of `node`
+fn subsumes(node: &TypingNode, literal: &TypingNode) -> bool {
+    matches!(
+        (node, literal),
+        (TypingNode::String, TypingNode::Literal(Literal::String(_)))
+            | (TypingNode::String, TypingNode::Format(_))
+            | (
+                TypingNode::Number(_),
+                TypingNode::Literal(Literal::Number(_))
+            )
+    )
+}
+
+fn set_optional(node: &mut TypingNode) {
+    match node {
+        TypingNode::ObjectEntry { optional, .. } | TypingNode::TupleElement { optional, .. } => {
+            *optional = true
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ijson::ijson;
+    use pretty_assertions::assert_eq;
+
+    use crate::{strategy::Strategy, Settings, Typing};
+
+    #[test]
+    fn merge() {
+        let mut typing = Typing::from_items(
+            "All",
+            ijson!([
+                { "id": 1, "kind": "a", "tags": ["x"], "owner": { "name": "n" } },
+                { "id": 2, "kind": "a", "tags": [], "owner": { "name": "n" } },
+            ]),
+        );
+        let other = Typing::from_items(
+            "All",
+            ijson!([
+                { "id": "3", "kind": "b", "owner": { "name": "m", "age": 3 }, "extra": null },
+                { "id": "4", "kind": "c", "owner": null, "extra": null },
+            ]),
+        );
+        typing.merge(&other);
+
+        self::assert_eq!(
+            [
+                "export interface All {",
+                "    id: number | string;",
+                "    kind: string;",
+                "    tags?: Array<string>;",
+                "    owner: AllOwner | null;",
+                "    extra?: null;",
+                "}",
+                "",
+                "export interface AllOwner {",
+                "    name: string;",
+                "    age?: number;",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.as_string(Strategy::Family)
+        );
+    }
+
+    #[test]
+    fn merge_variants() {
+        let settings = Settings {
+            discriminated_unions: true,
+            ..Settings::default()
+        };
+        let clicks = Typing::from_items_with_settings(
+            "All",
+            ijson!({ "events": [{ "type": "click", "x": 1 }, { "type": "click", "x": 2 }] }),
+            &settings,
+        );
+        let events = Typing::from_items_with_settings(
+            "All",
+            ijson!({
+                "events": [
+                    { "type": "click", "y": 1 },
+                    { "type": "scroll", "delta": 3 },
+                    { "type": "click", "y": 2 },
+                ]
+            }),
+            &settings,
+        );
+        let expected = [
+            "export interface All {",
+            "    events: Array<AllEventsClick | AllEventsScroll>;",
+            "}",
+            "",
+            "export interface AllEventsClick {",
+            "    type: \"click\";",
+            "    x?: number;",
+            "    y?: number;",
+            "}",
+            "",
+            "export interface AllEventsScroll {",
+            "    type: \"scroll\";",
+            "    delta: number;",
+            "}",
+            "",
+        ];
+
+        let mut typing = clicks.clone();
+        typing.merge_with_settings(&events, &settings);
+        self::assert_eq!(expected.join("\n"), typing.as_string(Strategy::Family));
+
+        let mut typing = events;
+        typing.merge_with_settings(&clicks, &settings);
+        let expected = expected.join("\n").replace(
+            "    x?: number;\n    y?: number;",
+            "    y?: number;\n    x?: number;",
+        );
+        self::assert_eq!(expected, typing.as_string(Strategy::Family));
+    }
+
+    #[test]
+    fn merge_root_variants() {
+        let settings = Settings {
+            discriminated_unions: true,
+            ..Settings::default()
+        };
+        let clicks = Typing::from_items_with_settings(
+            "All",
+            ijson!([{ "type": "click", "x": 1 }, { "type": "click", "x": 2 }]),
+            &settings,
+        );
+        let events = Typing::from_items_with_settings(
+            "All",
+            ijson!([
+                { "type": "hover", "x": 1 },
+                { "type": "scroll", "delta": 3 },
+                { "type": "hover", "x": 2 },
+            ]),
+            &settings,
+        );
+        let mut typing = clicks;
+        typing.merge_with_settings(&events, &settings);
+
+        self::assert_eq!(
+            [
+                "export type All = AllClick | AllHover | AllScroll;",
+                "",
+                "export interface AllClick {",
+                "    type: \"click\";",
+                "    x: number;",
+                "}",
+                "",
+                "export interface AllHover {",
+                "    type: \"hover\";",
+                "    x: number;",
+                "}",
+                "",
+                "export interface AllScroll {",
+                "    type: \"scroll\";",
+                "    delta: number;",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.as_string(Strategy::Family)
+        );
+    }
+
+    #[test]
+    fn merge_tuples_and_literals() {
+        let mut typing = Typing::from_items(
+            "All",
+            ijson!([
+                { "kind": "a", "point": [1, "a"] },
+                { "kind": "a", "point": [2, "b"] },
+            ]),
+        );
+        let other = Typing::from_items(
+            "All",
+            ijson!([
+                { "kind": "b", "point": [3, "c", true] },
+                { "kind": "b", "point": [4, "d", false] },
+            ]),
+        );
+        typing.merge(&other);
+
+        self::assert_eq!(
+            [
+                "export interface All {",
+                "    kind: \"a\" | \"b\";",
+                "    point: [number, string, boolean?];",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.as_string(Strategy::Family)
+        );
+    }
+
+    #[test]
+    fn merge_number_literals() {
+        let settings = Settings {
+            number_literal_max_values: 8,
+            ..Settings::default()
+        };
+        let mut typing = Typing::from_items_with_settings(
+            "All",
+            ijson!([{ "code": 1 }, { "code": 1 }]),
+            &settings,
+        );
+        let other = Typing::from_items_with_settings(
+            "All",
+            ijson!([{ "code": 2 }, { "code": 3 }]),
+            &settings,
+        );
+        typing.merge_with_settings(&other, &settings);
+
+        self::assert_eq!(
+            ["export interface All {", "    code: number;", "}", ""].join("\n"),
+            typing.as_string(Strategy::Family)
+        );
+    }
+
+    #[test]
+    fn merge_mixed_formats() {
+        let settings = Settings {
+            formats: true,
+            ..Settings::default()
+        };
+        let mut typing =
+            Typing::from_items_with_settings("All", ijson!([{ "at": "2021-10-03" }]), &settings);
+        let other = Typing::from_items_with_settings(
+            "All",
+            ijson!([{ "at": "2021-10-03T10:00:00Z" }]),
+            &settings,
+        );
+        typing.merge_with_settings(&other, &settings);
+
+        self::assert_eq!(
+            ["export interface All {", "    at: string;", "}", ""].join("\n"),
+            typing.as_string(Strategy::Family)
+        );
+    }
+}
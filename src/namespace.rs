@@ -1,16 +1,18 @@
-use std::fmt::Display;
+use std::fmt::{self, Write};
 
 use convert_case::{Case, Casing};
 use itertools::Itertools;
 
 use crate::utils::add_indentation;
 
-use super::{Interface, SETTINGS};
+use crate::{Interface, Settings};
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 pub struct Namespace {
     pub name: String,
     pub interface: Interface,
+    /// Union of variants declared instead of the interface
+    pub alias: Option<String>,
     pub entries: Vec<NamespaceEntry>,
 }
 
@@ -23,6 +25,7 @@ pub struct NamespaceEntry {
 #[derive(Debug, Hash, PartialOrd, Ord, PartialEq, Eq, Clone)]
 pub enum NamespaceEntryValue {
     Alias(String),
+    Enumeration(String),
     Namespace(String),
 }
 
@@ -31,20 +34,32 @@ impl Namespace {
         Self {
             name: name.to_case(Case::Pascal),
             interface: Interface::new(name, None),
+            alias: None,
             entries: vec![],
         }
     }
 }
 
-impl Display for Namespace {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let indentation = &SETTINGS.read().indentation;
+impl Namespace {
+    pub fn to_string(&self, settings: &Settings) -> String {
+        let mut s = String::new();
+        self.write(&mut s, settings).unwrap();
+
+        s
+    }
 
-        self.interface.fmt(f)?;
+    fn write<W: Write>(&self, f: &mut W, settings: &Settings) -> fmt::Result {
+        let indentation = &settings.indentation;
 
-        if self.entries.len() > 0 {
+        match &self.alias {
+            Some(alias) => write!(f, "export type {} = {};", self.name, alias)?,
+            None => self.interface.write(f, settings)?,
+        }
+
+        if !self.entries.is_empty() {
             write!(f, "\n\nexport namespace {} {{\n", self.name)?;
-            self.entries
+            let entries = self
+                .entries
                 .iter()
                 .sorted()
                 .map(|e| {
@@ -52,12 +67,15 @@ impl Display for Namespace {
                         NamespaceEntryValue::Alias(a) => {
                             format!("export type {} = {};\n", e.key, a)
                         }
-                        NamespaceEntryValue::Namespace(n) => format!("{}\n", n),
+                        NamespaceEntryValue::Enumeration(n) | NamespaceEntryValue::Namespace(n) => {
+                            format!("{}\n", n)
+                        }
                     };
 
                     add_indentation(indentation, &formated_value)
                 })
-                .join("\n").fmt(f)?;
+                .join("\n");
+            f.write_str(&entries)?;
 
             write!(f, "}}")
         } else {
@@ -0,0 +1,138 @@
+use ijson::INumber;
+use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
+use serde_json::Number;
+
+/// Largest integer javascript numbers represent exactly, `Number.MAX_SAFE_INTEGER`
+pub const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// How integers out of the safe integer range are typed in typescript and zod outputs,
+/// depending on how they are parsed
+#[derive(Serialize, Deserialize, Debug, Hash, PartialEq, Eq, Clone)]
+pub enum BigIntStyle {
+    /// `number`, numbers lose precision while strings stay `string`
+    Number,
+    /// `bigint`, for parsers reviving them as bigints
+    BigInt,
+    /// `string`, for parsers keeping them as strings
+    String,
+    /// `string & { readonly __format: "int64" }`
+    Branded,
+}
+
+/// Smallest integer type holding every observed value, `Float` when one of them has a
+/// decimal point or no integer type holds them all
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum NumberKind {
+    I32,
+    I64,
+    U64,
+    Float,
+}
+
+/// Kind and bounds of the numbers observed for a value
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct NumberRange {
+    pub kind: NumberKind,
+    pub min: OrderedFloat<f64>,
+    pub max: OrderedFloat<f64>,
+}
+
+impl NumberRange {
+    pub fn from_number(n: &INumber) -> Self {
+        let kind = match (n.has_decimal_point(), n.to_i32(), n.to_i64(), n.to_u64()) {
+            (false, Some(_), _, _) => NumberKind::I32,
+            (false, _, Some(_), _) => NumberKind::I64,
+            (false, _, _, Some(_)) => NumberKind::U64,
+            _ => NumberKind::Float,
+        };
+        let value = OrderedFloat(n.to_f64_lossy());
+
+        Self {
+            kind,
+            min: value,
+            max: value,
+        }
+    }
+
+    /// Range of every number, `None` when there are none
+    pub fn from_numbers<'a, T: IntoIterator<Item = &'a INumber>>(numbers: T) -> Option<Self> {
+        numbers
+            .into_iter()
+            .map(Self::from_number)
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// Smallest range holding the values of both ranges
+    pub fn union(&self, other: &Self) -> Self {
+        let min = self.min.min(other.min);
+        let kind = match self.kind.max(other.kind) {
+            // Negative numbers don't fit in an unsigned integer
+            NumberKind::U64 if min.0 < 0.0 => NumberKind::Float,
+            kind => kind,
+        };
+
+        Self {
+            kind,
+            min,
+            max: self.max.max(other.max),
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.kind != NumberKind::Float
+    }
+
+    /// Whether integers were seen out of the range javascript numbers represent exactly
+    pub fn is_unsafe(&self) -> bool {
+        let max_safe = MAX_SAFE_INTEGER as f64;
+
+        self.is_integer() && (self.max.0 > max_safe || self.min.0 < -max_safe)
+    }
+
+    /// Json number of a bound, without a decimal point for integers
+    pub fn bound(&self, value: OrderedFloat<f64>) -> Number {
+        match (self.is_integer(), value.0 < 0.0) {
+            (true, true) => Number::from(value.0 as i64),
+            (true, false) => Number::from(value.0 as u64),
+            _ => Number::from_f64(value.0).unwrap_or_else(|| Number::from(0)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ijson::ijson;
+
+    use super::*;
+
+    fn range(values: ijson::IValue) -> NumberRange {
+        let numbers = values.into_array().unwrap();
+        NumberRange::from_numbers(numbers.iter().filter_map(|v| v.as_number())).unwrap()
+    }
+
+    #[test]
+    fn kinds() {
+        assert_eq!(NumberKind::I32, range(ijson!([1, -2, 3])).kind);
+        assert_eq!(NumberKind::I64, range(ijson!([1, 5_000_000_000i64])).kind);
+        assert_eq!(NumberKind::U64, range(ijson!([1, u64::MAX])).kind);
+        assert_eq!(NumberKind::Float, range(ijson!([-1, u64::MAX])).kind);
+        assert_eq!(NumberKind::Float, range(ijson!([1, 2.5])).kind);
+    }
+
+    #[test]
+    fn is_unsafe() {
+        assert!(!range(ijson!([1, 9_007_199_254_740_991i64])).is_unsafe());
+        assert!(range(ijson!([1, 9_007_199_254_740_993i64])).is_unsafe());
+        assert!(range(ijson!([-9_007_199_254_740_993i64])).is_unsafe());
+        assert!(!range(ijson!([1e300])).is_unsafe());
+    }
+
+    #[test]
+    fn bounds() {
+        let range = range(ijson!([3, -2, 10]));
+
+        assert_eq!("-2", range.bound(range.min).to_string());
+        assert_eq!("10", range.bound(range.max).to_string());
+    }
+}
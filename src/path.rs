@@ -7,6 +7,12 @@ pub struct TypePath {
     pub segments: Vec<String>,
 }
 
+impl Default for TypePath {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TypePath {
     pub fn new() -> Self {
         Self {
@@ -1,48 +1,148 @@
 use std::{
     error::Error,
+    fmt,
     fs::{self, File},
-    io::BufReader,
-    path::Path,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
 };
 
 use ijson::IValue;
-use serde_json::{self, Value};
+use serde::de::{Deserializer as _, SeqAccess, Visitor};
+use serde_json::{self, Deserializer};
 
 #[allow(dead_code)]
 pub fn file(path: &Path) -> Result<IValue, Box<dyn Error>> {
     let reader = BufReader::new(File::open(path)?);
 
-    serde_json::from_reader(reader).or_else(|err| Err(Box::new(err) as Box<dyn std::error::Error>))
+    serde_json::from_reader(reader).map_err(|err| Box::new(err) as Box<dyn std::error::Error>)
 }
 
-#[allow(dead_code)]
-pub fn dir(folder: &Path) -> Result<Vec<Value>, Box<dyn Error>> {
-    fs::read_dir(folder)?
-        .into_iter()
+/// Calls `f` with every item of a file, one at a time, without loading the whole file in memory
+pub fn stream<F: FnMut(IValue)>(path: &Path, f: F) -> Result<(), Box<dyn Error>> {
+    let reader = BufReader::new(File::open(path)?);
+
+    items(reader, f)
+}
+
+/// Calls `f` with every item read: the elements of a top level array, or every value of a
+/// json lines (NDJSON) input
+pub fn items<R: BufRead, F: FnMut(IValue)>(mut reader: R, mut f: F) -> Result<(), Box<dyn Error>> {
+    let is_array = loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            return Ok(());
+        }
+
+        match buffer.iter().position(|b| !b.is_ascii_whitespace()) {
+            Some(i) => {
+                let is_array = buffer[i] == b'[';
+                reader.consume(i);
+                break is_array;
+            }
+            None => {
+                let len = buffer.len();
+                reader.consume(len);
+            }
+        }
+    };
+
+    let mut deserializer = Deserializer::from_reader(reader);
+    if is_array {
+        deserializer.deserialize_seq(ForEach(f))?;
+        deserializer.end()?;
+    } else {
+        for value in deserializer.into_iter::<IValue>() {
+            f(value?);
+        }
+    }
+
+    Ok(())
+}
+
+/// Visits the elements of an array without collecting them
+struct ForEach<F>(F);
+impl<'de, F: FnMut(IValue)> Visitor<'de> for ForEach<F> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an array")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(value) = seq.next_element::<IValue>()? {
+            (self.0)(value);
+        }
+
+        Ok(())
+    }
+}
+
+/// Json and json lines files of a folder, sorted by name
+pub fn dir(folder: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut paths: Vec<_> = fs::read_dir(folder)?
         .filter_map(Result::ok)
-        .filter(|entry| match entry.path().extension() {
-            Some(e) => e == "json",
-            None => false,
-        })
-        .map(|json_entry| json_entry.path())
-        .map(File::open)
-        .filter_map(|file| file.ok())
-        .map(BufReader::new)
-        .map(|json_reader| {
-            let result = serde_json::from_reader(json_reader);
-            result.or_else(|err| Err(Box::new(err) as Box<dyn std::error::Error>))
-        })
-        .collect()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_json(path))
+        .collect();
+    paths.sort();
+
+    Ok(paths)
+}
+
+/// Whether the path has a json extension
+pub fn is_json(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("json") | Some("ndjson") | Some("jsonl")
+    )
+}
+
+/// Whether the path has a json lines (NDJSON) extension
+pub fn is_json_lines(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("ndjson") | Some("jsonl")
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use std::path::Path;
 
+    use ijson::ijson;
+    use pretty_assertions::assert_eq;
+
     #[test]
     fn file() {
-        let value = super::file(&Path::new("data/sample_a.json"));
+        let value = super::file(Path::new("data/sample_a.json"));
 
         dbg!(&value);
     }
+
+    #[test]
+    fn items() {
+        let mut values = vec![];
+        super::items(" [{\"a\": 1}, 2]".as_bytes(), |v| values.push(v)).unwrap();
+        self::assert_eq!(values, vec![ijson!({ "a": 1 }), ijson!(2)]);
+
+        let mut values = vec![];
+        super::items("{\"a\": 1}\n{\"a\": [2]}\n".as_bytes(), |v| values.push(v)).unwrap();
+        self::assert_eq!(values, vec![ijson!({ "a": 1 }), ijson!({ "a": [2] })]);
+
+        let result = super::items("[1, 2".as_bytes(), |_| {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn dir() {
+        let paths = super::dir(Path::new("data")).unwrap();
+
+        self::assert_eq!(
+            paths,
+            vec![
+                Path::new("data/sample_a.json"),
+                Path::new("data/sample_c.json")
+            ]
+        );
+    }
 }
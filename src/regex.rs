@@ -3,6 +3,26 @@ use lazy_static::lazy_static;
 
 lazy_static! {
     pub static ref NUMBER: Regex = number();
+    pub static ref DYNAMIC_KEY: Regex = dynamic_key();
+    pub static ref IDENTIFIER: Regex = Regex::new(r"^[A-Za-z_$][\w$]*$").unwrap();
+    pub static ref DATE_TIME: Regex =
+        Regex::new(r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}(?::\d{2}(?:\.\d+)?)?(?:Z|[+-]\d{2}:\d{2})$")
+            .unwrap();
+    pub static ref INTEGER: Regex = Regex::new(r"^-?\d{16,20}$").unwrap();
+    pub static ref DATE: Regex = Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap();
+    pub static ref UUID: Regex =
+        Regex::new(r"(?i)^[\da-f]{8}-[\da-f]{4}-[\da-f]{4}-[\da-f]{4}-[\da-f]{12}$").unwrap();
+    pub static ref EMAIL: Regex = Regex::new(r"^[^\s@]+@[^\s@]+\.[^\s@]+$").unwrap();
+    pub static ref URI: Regex = Regex::new(r"(?i)^[a-z][a-z\d+.-]*://[^\s/?#]+\S*$").unwrap();
+    pub static ref IPV4: Regex = ipv4();
+    /// Eight groups, or fewer groups with exactly one `::`
+    pub static ref IPV6: Regex = Regex::new(
+        r"(?i)^(?:(?:[\da-f]{1,4}:){7}[\da-f]{1,4}|(?=(?:[\da-f]{0,4}:){2,8}[\da-f]{0,4}$)(?:[\da-f]{1,4}(?::[\da-f]{1,4})*)?::(?:[\da-f]{1,4}(?::[\da-f]{1,4})*)?)$"
+    )
+    .unwrap();
+    pub static ref HEX_COLOR: Regex =
+        Regex::new(r"^#(?:[\da-fA-F]{3,4}|[\da-fA-F]{6}|[\da-fA-F]{8})$").unwrap();
+    pub static ref BASE64: Regex = base64();
 }
 
 fn number() -> Regex {
@@ -21,3 +41,57 @@ fn number() -> Regex {
 
     Regex::new(&regex).unwrap()
 }
+
+/// Keys that look generated rather than named: ids, hashes, uuids and dates
+fn dynamic_key() -> Regex {
+    let id = r"[a-z_]{0,3}[-_]?\d+";
+    let hash = r"[\da-f]{16,}";
+    let uuid = r"[\da-f]{8}-[\da-f]{4}-[\da-f]{4}-[\da-f]{4}-[\da-f]{12}";
+    let date = r"\d{4}-\d{2}-\d{2}(?:[t ][\d:.]+z?)?";
+
+    let regex = format!(r"(?i)^(?:{}|{}|{}|{})$", id, hash, uuid, date);
+
+    Regex::new(&regex).unwrap()
+}
+
+fn ipv4() -> Regex {
+    let byte = r"(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)";
+
+    Regex::new(&format!(r"^(?:{0}\.){{3}}{0}$", byte)).unwrap()
+}
+
+/// Padded base64 of at least 16 characters mixing cases and digits, so that words and
+/// hexadecimal hashes aren't mistaken for it
+fn base64() -> Regex {
+    let mixed = r"(?=.*[A-Z])(?=.*[a-z])(?=.*\d)";
+    let chunk = r"[A-Za-z\d+/]";
+
+    let regex = format!(
+        r"^{0}(?:{1}{{4}}){{3,}}(?:{1}{{4}}|{1}{{3}}=|{1}{{2}}==)$",
+        mixed, chunk
+    );
+
+    Regex::new(&regex).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dynamic_key() {
+        let matches = |s| DYNAMIC_KEY.is_match(s).unwrap();
+
+        assert!(matches("u123"));
+        assert!(matches("42"));
+        assert!(matches("id_7"));
+        assert!(matches("5d41402abc4b2a76b9719d911017c592"));
+        assert!(matches("123e4567-e89b-12d3-a456-426614174000"));
+        assert!(matches("2021-10-03"));
+        assert!(matches("2021-10-03T10:00:00Z"));
+
+        assert!(!matches("name"));
+        assert!(!matches("GlossEntry"));
+        assert!(!matches("address"));
+    }
+}
@@ -4,11 +4,11 @@ use config::{Config, ConfigError, Environment, File, FileFormat};
 use lazy_static::lazy_static;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use serde_json;
 
-use crate::strategy::Strategy;
+use crate::{strategy::Strategy, ArraySyntax, BigIntStyle, FormatStyle, LiteralStyle, NullStyle};
 
 lazy_static! {
+    /// Settings filled by the command line, the library only reads the settings passed to it
     pub static ref SETTINGS: RwLock<Settings> = RwLock::new(Settings::new());
 }
 
@@ -19,6 +19,61 @@ pub struct Settings {
     pub typescript_version: String,
     pub strategy: Strategy,
     pub sort: bool,
+    pub deduplicate: bool,
+    pub record_threshold: usize,
+    /// Writes records as `{ [key: string]: T }` index signatures instead of `Record<string, T>`
+    pub index_signatures: bool,
+    pub stream: bool,
+    pub chunk_size: usize,
+    pub separate: bool,
+    pub split: bool,
+    /// Most distinct strings typed as a union of literals, 0 for no limit
+    pub literal_max_values: usize,
+    /// Longest string typed as a literal, 0 disables literals
+    pub literal_max_length: usize,
+    /// Minimum percentage of repeated strings for them to be typed as literals
+    pub literal_min_repetition: usize,
+    /// Enables template literal types for strings containing numbers
+    pub templates: bool,
+    /// Longest string typed as a template literal
+    pub template_max_length: usize,
+    /// Percentage of distinct strings containing numbers above which they become templates
+    pub template_min_numeric: usize,
+    /// Most distinct integers typed as a union of literals, 0 disables number literals
+    pub number_literal_max_values: usize,
+    /// Minimum percentage of repeated integers for them to be typed as literals
+    pub number_literal_min_repetition: usize,
+    /// How sets of literals are declared in typescript outputs
+    pub literal_style: LiteralStyle,
+    /// Detects dates, uuids, urls, emails, ips, hex colors and base64 strings
+    pub formats: bool,
+    /// How string formats and integers are rendered in typescript outputs
+    pub format_style: FormatStyle,
+    /// Types dates as chrono types in rust and as coerced dates in zod instead of strings
+    pub dates: bool,
+    /// Adds the observed minimum and maximum of numbers to the outputs
+    pub number_bounds: bool,
+    /// How integers out of the range javascript numbers represent exactly are typed
+    pub bigint_style: BigIntStyle,
+    /// Splits objects told apart by a literal key into a union of one interface per value
+    pub discriminated_unions: bool,
+    /// How keys missing or null in some objects are typed
+    pub null_style: NullStyle,
+    /// Most members of a union written inline instead of as a namespace alias by the tree
+    /// strategy, 0 always declares an alias
+    pub inline_union_max_members: usize,
+    /// Longest union written inline by the tree strategy
+    pub inline_union_max_length: usize,
+    /// Marks properties, arrays, records and tuples as readonly in typescript outputs
+    pub readonly: bool,
+    /// Syntax of array types in typescript outputs
+    pub array_syntax: ArraySyntax,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[allow(dead_code)]
@@ -30,6 +85,33 @@ impl Settings {
             typescript_version: String::from("latest"),
             strategy: Strategy::Tree,
             sort: false,
+            deduplicate: false,
+            record_threshold: 8,
+            index_signatures: false,
+            stream: false,
+            chunk_size: 10000,
+            separate: false,
+            split: false,
+            literal_max_values: 0,
+            literal_max_length: 16,
+            literal_min_repetition: 0,
+            templates: true,
+            template_max_length: 32,
+            template_min_numeric: 50,
+            number_literal_max_values: 0,
+            number_literal_min_repetition: 75,
+            literal_style: LiteralStyle::Union,
+            formats: false,
+            format_style: FormatStyle::Plain,
+            dates: false,
+            number_bounds: false,
+            bigint_style: BigIntStyle::Number,
+            discriminated_unions: false,
+            null_style: NullStyle::Separate,
+            inline_union_max_members: 0,
+            inline_union_max_length: 48,
+            readonly: false,
+            array_syntax: ArraySyntax::Generic,
         }
     }
 
@@ -69,6 +151,42 @@ impl Settings {
 
         settings.try_into()
     }
+
+    /// Whether the targeted typescript version is at least `major.minor`, `latest` supports
+    /// every feature
+    pub fn typescript_at_least(&self, major: u64, minor: u64) -> bool {
+        match Self::parse_typescript_version(&self.typescript_version) {
+            Some(version) => version >= (major, minor),
+            None => true,
+        }
+    }
+
+    /// Major and minor of a `major[.minor[.patch]]` typescript version, optionally prefixed by
+    /// `v`, `None` for `latest` or anything else
+    pub fn parse_typescript_version(version: &str) -> Option<(u64, u64)> {
+        let parts: Vec<_> = version
+            .trim_start_matches('v')
+            .split('.')
+            .map(|part| match part.chars().all(|c| c.is_ascii_digit()) {
+                true => part.parse::<u64>().ok(),
+                false => None,
+            })
+            .collect::<Option<_>>()?;
+
+        match parts.as_slice() {
+            [major] => Some((*major, 0)),
+            [major, minor] | [major, minor, _] => Some((*major, *minor)),
+            _ => None,
+        }
+    }
+
+    /// Most distinct strings typed as a union of literals, `usize::MAX` when unlimited
+    pub fn max_literal_values(&self) -> usize {
+        match self.literal_max_values {
+            0 => usize::MAX,
+            max_values => max_values,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -91,6 +209,33 @@ mod tests {
             typescript_version: String::from("latest"),
             strategy: Strategy::Tree,
             sort: false,
+            deduplicate: false,
+            record_threshold: 8,
+            index_signatures: false,
+            stream: false,
+            chunk_size: 10000,
+            separate: false,
+            split: false,
+            literal_max_values: 0,
+            literal_max_length: 16,
+            literal_min_repetition: 0,
+            templates: true,
+            template_max_length: 32,
+            template_min_numeric: 50,
+            number_literal_max_values: 0,
+            number_literal_min_repetition: 75,
+            literal_style: LiteralStyle::Union,
+            formats: false,
+            format_style: FormatStyle::Plain,
+            dates: false,
+            number_bounds: false,
+            bigint_style: BigIntStyle::Number,
+            discriminated_unions: false,
+            null_style: NullStyle::Separate,
+            inline_union_max_members: 0,
+            inline_union_max_length: 48,
+            readonly: false,
+            array_syntax: ArraySyntax::Generic,
         };
         assert_eq!(expected, a.merge(b).unwrap())
     }
@@ -113,4 +258,31 @@ mod tests {
             Settings::from_config("config/test.toml").unwrap(),
         );
     }
+
+    #[test]
+    fn typescript_at_least() {
+        let version = |v: &str| Settings {
+            typescript_version: String::from(v),
+            ..Settings::default()
+        };
+
+        assert!(version("latest").typescript_at_least(4, 1));
+        assert!(version("4.1.2").typescript_at_least(4, 1));
+        assert!(version("v4.2").typescript_at_least(4, 1));
+        assert!(version("5").typescript_at_least(4, 1));
+        assert!(!version("4.0").typescript_at_least(4, 1));
+        assert!(!version("3").typescript_at_least(3, 4));
+        assert!(!version("4").typescript_at_least(4, 1));
+    }
+
+    #[test]
+    fn parse_typescript_version() {
+        assert_eq!(Settings::parse_typescript_version("4.1.2"), Some((4, 1)));
+        assert_eq!(Settings::parse_typescript_version("v3"), Some((3, 0)));
+        assert_eq!(Settings::parse_typescript_version("latest"), None);
+        assert_eq!(Settings::parse_typescript_version("4.x"), None);
+        assert_eq!(Settings::parse_typescript_version("4..1"), None);
+        assert_eq!(Settings::parse_typescript_version("+4"), None);
+        assert_eq!(Settings::parse_typescript_version("4.1.2.3"), None);
+    }
 }
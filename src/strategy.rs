@@ -1,4 +1,8 @@
-use std::collections::HashMap;
+mod json_schema;
+mod rust;
+mod zod;
+
+use std::collections::{HashMap, HashSet};
 #[allow(unused_imports)]
 use std::{
     cmp::Ordering,
@@ -12,42 +16,352 @@ use itertools::Itertools;
 use petgraph::{
     graph::NodeIndex,
     visit::{Reversed, Topo},
+    Direction,
 };
 use serde::{Deserialize, Serialize};
 
 #[allow(unused_imports)]
-use crate::{utils, Typing, SETTINGS};
+use crate::{utils, Settings, Typing};
 use crate::{
-    Interface, InterfaceEntry, Namespace, NamespaceEntry, NamespaceEntryValue, TypingNode,
-    TypingUnion,
+    ArraySyntax, BigIntStyle, Enumeration, FormatStyle, Interface, InterfaceEntry, Literal,
+    LiteralStyle, Namespace, NamespaceEntry, NamespaceEntryValue, NumberRange, StringFormat,
+    TypePath, TypingNode, TypingUnion,
 };
 
+use json_schema::JsonSchema;
+use rust::Rust;
+use zod::Zod;
+
 #[derive(Serialize, Deserialize, Debug, Hash, PartialEq, Eq, Clone)]
 pub enum Strategy {
     Family,
     Tree,
+    JsonSchema,
+    Rust,
+    Zod,
 }
 
 pub trait TypingStrategy {
-    fn fmt_typing(typing: &Typing) -> String;
+    fn fmt_typing(typing: &Typing, settings: &Settings) -> String;
+
+    /// Formats several root typings into a single output
+    fn fmt_typings(typings: &[Typing], settings: &Settings) -> String {
+        typings
+            .iter()
+            .map(|typing| Self::fmt_typing(typing, settings))
+            .join("\n")
+    }
+
+    /// Formats several root typings into one module per top level declaration, `None` when
+    /// the output can't be split
+    fn fmt_modules(_typings: &[Typing], _settings: &Settings) -> Option<Vec<Module>> {
+        None
+    }
+}
+
+/// Typescript file of a split output
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct Module {
+    pub name: String,
+    pub content: String,
+}
+
+/// Top level declaration of an output along with the names it exports and the names of
+/// other declarations it references
+struct Declaration {
+    name: String,
+    exports: Vec<String>,
+    references: Vec<String>,
+    content: String,
+}
+
+/// One module per declaration importing the exports of the other declarations it references,
+/// and an `index` module re-exporting all of them. `header` lines start every module.
+fn modules(declarations: Vec<Declaration>, header: &str, settings: &Settings) -> Vec<Module> {
+    let delimiter = &settings.string_delimiter;
+
+    let mut modules: Vec<_> = declarations
+        .iter()
+        .map(|declaration| {
+            let imports = declarations
+                .iter()
+                .filter(|other| other.name != declaration.name)
+                .filter_map(|other| {
+                    let exports: Vec<_> = other
+                        .exports
+                        .iter()
+                        .filter(|export| declaration.references.contains(export))
+                        .collect();
+
+                    match exports.len() {
+                        0 => None,
+                        _ => Some(format!(
+                            "import {{ {} }} from {2}./{1}{2};\n",
+                            exports.iter().join(", "),
+                            other.name,
+                            delimiter
+                        )),
+                    }
+                })
+                .join("");
+
+            let imports = format!("{}{}", header, imports);
+            let content = match imports.len() {
+                0 => format!("{}\n", declaration.content),
+                _ => format!("{}\n{}\n", imports, declaration.content),
+            };
+
+            Module {
+                name: declaration.name.clone(),
+                content,
+            }
+        })
+        .collect();
+
+    let index = declarations
+        .iter()
+        .map(|declaration| format!("export * from {1}./{0}{1};\n", declaration.name, delimiter))
+        .join("");
+    modules.push(Module {
+        name: String::from("index"),
+        content: index,
+    });
+
+    modules
 }
 
 #[derive(Serialize, Deserialize, Debug, Hash, PartialEq, Eq, Clone)]
 struct Family;
 impl TypingStrategy for Family {
-    fn fmt_typing(_typing: &Typing) -> String {
-        todo!()
+    fn fmt_typing(typing: &Typing, settings: &Settings) -> String {
+        Family::fmt_typings(std::slice::from_ref(typing), settings)
+    }
+
+    fn fmt_typings(typings: &[Typing], settings: &Settings) -> String {
+        let declarations = Family::all_declarations(typings, settings)
+            .into_iter()
+            .map(|declaration| declaration.content)
+            .join("\n\n");
+
+        format!("{}\n", declarations)
+    }
+
+    fn fmt_modules(typings: &[Typing], settings: &Settings) -> Option<Vec<Module>> {
+        let declarations = Family::all_declarations(typings, settings);
+
+        Some(modules(declarations, "", settings))
+    }
+}
+
+impl Family {
+    /// Names and declarations of every typing, named together so that no two objects share
+    /// a name
+    fn all_declarations(typings: &[Typing], settings: &Settings) -> Vec<Declaration> {
+        let names = Family::object_names(typings);
+        let mut taken: HashSet<String> = names.iter().flat_map(|n| n.values().cloned()).collect();
+
+        typings
+            .iter()
+            .zip(&names)
+            .flat_map(|(typing, names)| Family::declarations(typing, names, &mut taken, settings))
+            .collect()
+    }
+
+    /// Declarations of the interface, or the union of variants, of every object of a typing
+    /// in depth first order, each one followed by the enumerations of its literals.
+    /// Enumerations are named apart from the `taken` names.
+    fn declarations(
+        typing: &Typing,
+        names: &HashMap<NodeIndex, String>,
+        taken: &mut HashSet<String>,
+        settings: &Settings,
+    ) -> Vec<Declaration> {
+        let graph = &typing.graph;
+
+        let graph_adaptor = Reversed(graph);
+        let mut topo = Topo::new(&graph_adaptor);
+
+        let mut node_value: HashMap<NodeIndex, String> = HashMap::new();
+        let mut declarations = HashMap::new();
+        let mut enumerations: HashMap<NodeIndex, Vec<Enumeration>> = HashMap::new();
+        while let Some(nx) = topo.next(&graph_adaptor) {
+            let mut values = |object_node: &NodeIndex| -> Vec<String> {
+                let set = literal_set(typing, nx, settings).map(|(literals, values)| {
+                    let name = format!("{}{}", names[object_node], enumeration_key(typing, nx));
+                    let name = (1..)
+                        .map(|count| match count {
+                            1 => name.clone(),
+                            _ => format!("{}{}", name, count),
+                        })
+                        .find(|name| !taken.contains(name))
+                        .unwrap();
+
+                    taken.insert(name.clone());
+                    enumerations
+                        .entry(*object_node)
+                        .or_default()
+                        .push(Enumeration::new(&name, values));
+
+                    (literals, name)
+                });
+
+                typing
+                    .children(nx)
+                    .filter_map(|n_nx| match &set {
+                        Some((literals, name)) if literals.contains(&n_nx) => Some(name.clone()),
+                        _ => node_value.get(&n_nx).cloned(),
+                    })
+                    .unique()
+                    .collect()
+            };
+
+            let value = match &graph[nx] {
+                TypingNode::Null => String::from("null"),
+                TypingNode::Boolean => String::from("boolean"),
+                TypingNode::Number(range) => number(range, settings),
+                TypingNode::String => String::from("string"),
+                TypingNode::Literal(l) => literal(l, settings),
+                TypingNode::Format(f) => string_format(f, settings),
+                node @ (TypingNode::Array { object_node, .. }
+                | TypingNode::Record { object_node, .. }) => {
+                    let values = values(object_node);
+
+                    match values.len() {
+                        0 => collection(node, unknown(settings), settings),
+                        _ => collection(node, &values.join(" | "), settings),
+                    }
+                }
+                TypingNode::Tuple { .. } => tuple(typing, nx, &node_value, settings),
+                // Null is added back according to the null style
+                TypingNode::ObjectEntry { object_node, .. } => values(object_node)
+                    .into_iter()
+                    .filter(|value| value != "null")
+                    .join(" | "),
+                TypingNode::TupleElement { object_node, .. } => values(object_node).join(" | "),
+                TypingNode::Object(_) if typing.is_union(nx) => {
+                    let name = &names[&nx];
+                    let variants = typing.children(nx).map(|n_nx| &node_value[&n_nx]);
+                    let alias = format!("export type {} = {};", name, variants.format(" | "));
+                    declarations.insert(nx, (name.clone(), alias));
+
+                    name.clone()
+                }
+                TypingNode::Object(_) => {
+                    let name = &names[&nx];
+                    let mut interface = Interface::new(name, None);
+                    interface.entries = typing
+                        .children(nx)
+                        .map(|n_nx| {
+                            interface_entry(typing, n_nx, node_value[&n_nx].clone(), settings)
+                        })
+                        .collect();
+                    let value = interface.name.clone();
+                    declarations.insert(nx, (value.clone(), interface.to_string(settings)));
+
+                    value
+                }
+            };
+
+            node_value.insert(nx, value);
+        }
+
+        typing
+            .object_paths()
+            .into_iter()
+            .filter_map(|(nx, _)| Some((nx, declarations.remove(&nx)?)))
+            .flat_map(|(nx, (name, content))| {
+                let enumerations = enumerations.remove(&nx).unwrap_or_default();
+                let references = referenced_objects(typing, nx)
+                    .into_iter()
+                    .map(|n_nx| names[&n_nx].clone())
+                    .chain(enumerations.iter().map(|e| e.name.clone()))
+                    .collect();
+                let declaration = Declaration {
+                    exports: vec![name.clone()],
+                    name,
+                    references,
+                    content,
+                };
+
+                std::iter::once(declaration).chain(enumerations.into_iter().map(|enumeration| {
+                    Declaration {
+                        exports: vec![enumeration.name.clone()],
+                        references: vec![],
+                        content: enumeration.to_string(settings),
+                        name: enumeration.name,
+                    }
+                }))
+            })
+            .collect()
+    }
+
+    /// Names every object of every typing from its closest ancestors, growing the ancestry
+    /// until all the names are unique across the typings
+    fn object_names(typings: &[Typing]) -> Vec<HashMap<NodeIndex, String>> {
+        let paths: Vec<_> = typings
+            .iter()
+            .enumerate()
+            .flat_map(|(i, typing)| {
+                typing
+                    .object_paths()
+                    .into_iter()
+                    .map(move |(nx, path)| ((i, nx), path))
+            })
+            .collect();
+        let mut depths = vec![2; paths.len()];
+
+        let name = |path: &TypePath, depth: usize| -> String {
+            let start = path.segments.len().saturating_sub(depth);
+            path.segments[start..].concat()
+        };
+
+        loop {
+            let names: Vec<_> = paths
+                .iter()
+                .zip(&depths)
+                .map(|((_, path), depth)| name(path, *depth))
+                .collect();
+            let counts = names.iter().counts();
+
+            let mut grown = false;
+            for (i, (_, path)) in paths.iter().enumerate() {
+                if counts[&names[i]] > 1 && depths[i] < path.segments.len() {
+                    depths[i] += 1;
+                    grown = true;
+                }
+            }
+
+            if !grown {
+                let mut seen: HashMap<String, usize> = HashMap::new();
+                let mut typing_names = vec![HashMap::new(); typings.len()];
+
+                for (((i, nx), _), name) in paths.iter().zip(names) {
+                    let count = seen.entry(name.clone()).or_insert(0);
+                    *count += 1;
+
+                    let name = match count {
+                        1 => name,
+                        _ => format!("{}{}", name, count),
+                    };
+                    typing_names[*i].insert(*nx, name);
+                }
+
+                return typing_names;
+            }
+        }
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, Hash, PartialEq, Eq, Clone)]
 struct Tree;
 impl TypingStrategy for Tree {
-    fn fmt_typing<'a>(typing: &Typing) -> String {
+    fn fmt_typing(typing: &Typing, settings: &Settings) -> String {
         let graph = &typing.graph;
         let graph_adaptor = Reversed(graph);
         let mut topo = Topo::new(&graph_adaptor);
 
+        let paths: HashMap<_, _> = typing.object_paths().into_iter().collect();
+
         let mut node_value = HashMap::new();
         let mut node_namespace = HashMap::new();
         while let Some(nx) = topo.next(&graph_adaptor) {
@@ -58,43 +372,48 @@ impl TypingStrategy for Tree {
                 TypingNode::Boolean => {
                     node_value.insert(nx, String::from("boolean"));
                 }
-                TypingNode::Number => {
-                    node_value.insert(nx, String::from("number"));
+                TypingNode::Number(range) => {
+                    node_value.insert(nx, number(range, settings));
                 }
                 TypingNode::String => {
                     node_value.insert(nx, String::from("string"));
                 }
-                TypingNode::Array { object_node, key } => {
+                TypingNode::Array { object_node, key }
+                | TypingNode::Record { object_node, key } => {
                     let object_name = graph[*object_node].as_object().unwrap();
-                    let values: Vec<_> = graph
-                        .neighbors(nx)
-                        .filter_map(|n_nx| node_value.remove(&n_nx).map(|v| (v, n_nx)))
-                        .map(|(value, n_nx)| {
-                            if node_namespace.contains_key(&n_nx) {
-                                let namespace_value = format!("{}.{}", object_name, value);
-                                let o_namespace =
-                                    format!("{}", node_namespace.remove(&n_nx).unwrap());
-                                node_namespace
-                                    .entry(*object_node)
-                                    .or_insert(Namespace::new(object_name))
-                                    .entries
-                                    .push(NamespaceEntry::new(
-                                        &value,
-                                        NamespaceEntryValue::Namespace(o_namespace),
-                                    ));
-
-                                namespace_value
-                            } else {
-                                value
+                    let set =
+                        Tree::enumeration(typing, nx, *object_node, &mut node_namespace, settings);
+                    let values: Vec<_> = typing
+                        .children(nx)
+                        .filter_map(|n_nx| node_value.get(&n_nx).cloned().map(|v| (v, n_nx)))
+                        .map(|(value, n_nx)| match &set {
+                            Some((literals, reference)) if literals.contains(&n_nx) => {
+                                reference.clone()
                             }
+                            _ => Tree::reference(
+                                &paths,
+                                &mut node_namespace,
+                                *object_node,
+                                value,
+                                n_nx,
+                                settings,
+                            ),
                         })
+                        .unique()
                         .collect();
 
                     node_value.insert(
                         nx,
                         match values.len() {
-                            0 => String::from("unknown"),
-                            1 => values.into_iter().nth(0).unwrap(),
+                            0 => collection(&graph[nx], unknown(settings), settings),
+                            1 => {
+                                let value = values.into_iter().next().unwrap();
+                                collection(&graph[nx], &value, settings)
+                            }
+                            // The key already names the enumeration
+                            _ if set.is_some() || Tree::inline(&values, settings) => {
+                                collection(&graph[nx], &values.join(" | "), settings)
+                            }
                             _ => {
                                 let key = key.to_case(Case::Pascal);
                                 let namespace = node_namespace
@@ -102,33 +421,47 @@ impl TypingStrategy for Tree {
                                     .or_insert(Namespace::new(object_name));
 
                                 let typing_union = TypingUnion::new(values);
-                                let namespace_value =
-                                    NamespaceEntryValue::Alias(typing_union.to_string("    "));
+                                let namespace_value = NamespaceEntryValue::Alias(
+                                    typing_union.to_string(&settings.indentation),
+                                );
                                 namespace
                                     .entries
                                     .push(NamespaceEntry::new(&key, namespace_value));
 
-                                format!("Array<{}.{}>", object_name, key)
+                                let value = format!("{}.{}", object_name, key);
+                                collection(&graph[nx], &value, settings)
                             }
                         },
                     );
                 }
                 TypingNode::Literal(l) => {
-                    node_value.insert(nx, format!("{}", l));
+                    node_value.insert(nx, literal(l, settings));
+                }
+                TypingNode::Format(f) => {
+                    node_value.insert(nx, string_format(f, settings));
+                }
+                TypingNode::Object(name) if typing.is_union(nx) => {
+                    let variants: Vec<_> = typing
+                        .children(nx)
+                        .map(|n_nx| {
+                            let value = node_value[&n_nx].clone();
+                            Tree::reference(&paths, &mut node_namespace, nx, value, n_nx, settings)
+                        })
+                        .collect();
+
+                    let namespace = node_namespace.entry(nx).or_insert(Namespace::new(name));
+                    namespace.alias =
+                        Some(TypingUnion::new(variants).to_string(&settings.indentation));
+
+                    node_value.insert(nx, name.clone());
                 }
                 TypingNode::Object(name) => {
                     let mut interface = Interface::new(name, None);
-                    interface.entries = graph
-                        .neighbors(nx)
+                    interface.entries = typing
+                        .children(nx)
                         .map(|n_nx| {
-                            let (key, optional) = graph[n_nx].as_object_entry().unwrap();
                             let value = node_value.remove(&n_nx).unwrap();
-
-                            InterfaceEntry {
-                                key: String::from(key),
-                                optional,
-                                value,
-                            }
+                            interface_entry(typing, n_nx, value, settings)
                         })
                         .collect();
 
@@ -143,34 +476,38 @@ impl TypingStrategy for Tree {
                     object_node,
                 } => {
                     let object_name = graph[*object_node].as_object().unwrap();
-                    let values: Vec<_> = graph
-                        .neighbors(nx)
-                        .filter_map(|n_nx| node_value.remove(&n_nx).map(|v| (v, n_nx)))
-                        .map(|(value, n_nx)| {
-                            if node_namespace.contains_key(&n_nx) {
-                                let namespace_value = format!("{}.{}", object_name, value);
-                                let o_namespace =
-                                    format!("{}", node_namespace.remove(&n_nx).unwrap());
-                                node_namespace
-                                    .entry(*object_node)
-                                    .or_insert(Namespace::new(object_name))
-                                    .entries
-                                    .push(NamespaceEntry::new(
-                                        &value,
-                                        NamespaceEntryValue::Namespace(o_namespace),
-                                    ));
-
-                                namespace_value
-                            } else {
-                                value
+                    let set =
+                        Tree::enumeration(typing, nx, *object_node, &mut node_namespace, settings);
+                    // Null is added back according to the null style
+                    let values: Vec<_> = typing
+                        .children(nx)
+                        .filter(|n_nx| graph[*n_nx] != TypingNode::Null)
+                        .filter_map(|n_nx| node_value.get(&n_nx).cloned().map(|v| (v, n_nx)))
+                        .map(|(value, n_nx)| match &set {
+                            Some((literals, reference)) if literals.contains(&n_nx) => {
+                                reference.clone()
                             }
+                            _ => Tree::reference(
+                                &paths,
+                                &mut node_namespace,
+                                *object_node,
+                                value,
+                                n_nx,
+                                settings,
+                            ),
                         })
+                        .unique()
                         .collect();
 
                     node_value.insert(
                         nx,
                         match values.len() {
-                            1 => values.into_iter().nth(0).unwrap(),
+                            0 => String::new(),
+                            1 => values.into_iter().next().unwrap(),
+                            // The key already names the enumeration
+                            _ if set.is_some() || Tree::inline(&values, settings) => {
+                                values.join(" | ")
+                            }
                             _ => {
                                 let key = key.to_case(Case::Pascal);
                                 let namespace = node_namespace
@@ -178,8 +515,9 @@ impl TypingStrategy for Tree {
                                     .or_insert(Namespace::new(object_name));
 
                                 let typing_union = TypingUnion::new(values);
-                                let namespace_value =
-                                    NamespaceEntryValue::Alias(typing_union.to_string("    "));
+                                let namespace_value = NamespaceEntryValue::Alias(
+                                    typing_union.to_string(&settings.indentation),
+                                );
                                 namespace
                                     .entries
                                     .push(NamespaceEntry::new(&key, namespace_value));
@@ -189,18 +527,1325 @@ impl TypingStrategy for Tree {
                         },
                     );
                 }
+                TypingNode::Tuple { .. } => {
+                    node_value.insert(nx, tuple(typing, nx, &node_value, settings));
+                }
+                TypingNode::TupleElement { object_node, .. } => {
+                    let set =
+                        Tree::enumeration(typing, nx, *object_node, &mut node_namespace, settings);
+                    let value = typing
+                        .children(nx)
+                        .filter_map(|n_nx| node_value.get(&n_nx).cloned().map(|v| (v, n_nx)))
+                        .map(|(value, n_nx)| match &set {
+                            Some((literals, reference)) if literals.contains(&n_nx) => {
+                                reference.clone()
+                            }
+                            _ => Tree::reference(
+                                &paths,
+                                &mut node_namespace,
+                                *object_node,
+                                value,
+                                n_nx,
+                                settings,
+                            ),
+                        })
+                        .unique()
+                        .join(" | ");
+
+                    node_value.insert(nx, value);
+                }
             };
         }
 
-        format!("{}\n", node_namespace.remove(&NodeIndex::new(0)).unwrap())
+        let root = node_namespace.remove(&NodeIndex::new(0)).unwrap();
+
+        format!("{}\n", root.to_string(settings))
+    }
+
+    fn fmt_modules(typings: &[Typing], settings: &Settings) -> Option<Vec<Module>> {
+        let declarations = typings
+            .iter()
+            .map(|typing| {
+                let name = String::from(typing.graph[NodeIndex::new(0)].as_object().unwrap());
+
+                // Every root is declared in its own namespace
+                Declaration {
+                    exports: vec![name.clone()],
+                    name,
+                    references: vec![],
+                    content: Tree::fmt_typing(typing, settings).trim_end().to_string(),
+                }
+            })
+            .collect();
+
+        Some(modules(declarations, "", settings))
+    }
+}
+
+/// Objects referenced by the object `nx`, through its entries but not through other objects
+fn referenced_objects(typing: &Typing, nx: NodeIndex) -> Vec<NodeIndex> {
+    let mut objects = vec![];
+    let mut stack: Vec<_> = typing.children(nx).collect();
+    while let Some(n_nx) = stack.pop() {
+        match typing.graph[n_nx] {
+            TypingNode::Object(_) => objects.push(n_nx),
+            _ => stack.extend(typing.children(n_nx)),
+        }
+    }
+
+    objects
+}
+
+/// Wraps the type of the elements of an array or the values of a record, records are index
+/// signatures with the index signatures option and before typescript 2.1
+fn collection(node: &TypingNode, value: &str, settings: &Settings) -> String {
+    let record_signature = settings.index_signatures || !settings.typescript_at_least(2, 1);
+
+    match (node, settings.readonly) {
+        (TypingNode::Record { .. }, false) if record_signature => {
+            format!("{{ [key: string]: {} }}", value)
+        }
+        (TypingNode::Record { .. }, true) if record_signature => {
+            format!("{{ readonly [key: string]: {} }}", value)
+        }
+        (TypingNode::Record { .. }, false) => format!("Record<string, {}>", value),
+        (TypingNode::Record { .. }, true) => format!("Readonly<Record<string, {}>>", value),
+        (_, readonly) => array(value, readonly, settings),
+    }
+}
+
+/// Array of `value` in the configured syntax, readonly arrays stay `ReadonlyArray<T>` before
+/// typescript 3.4
+fn array(value: &str, readonly: bool, settings: &Settings) -> String {
+    let shorthand = match settings.array_syntax {
+        ArraySyntax::Generic => false,
+        ArraySyntax::Array => true,
+        ArraySyntax::ArraySimple => is_simple(value),
+    };
+    let shorthand = shorthand && (!readonly || settings.typescript_at_least(3, 4));
+
+    let readonly = if readonly { "readonly " } else { "" };
+    match (shorthand, needs_parentheses(value)) {
+        (true, true) => format!("{}({})[]", readonly, value),
+        (true, false) => format!("{}{}[]", readonly, value),
+        (false, _) if readonly.is_empty() => format!("Array<{}>", value),
+        (false, _) => format!("ReadonlyArray<{}>", value),
+    }
+}
+
+/// Whether a type is a keyword or a name, possibly of an array, as opposed to literals,
+/// unions or generic types
+fn is_simple(value: &str) -> bool {
+    let name = value.trim_end_matches("[]");
+
+    name.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$' || c == '.')
+}
+
+/// Whether a type binds looser than the brackets of `T[]`, such as unions, intersections
+/// and readonly types
+fn needs_parentheses(value: &str) -> bool {
+    if value.starts_with("readonly ") {
+        return true;
+    }
+
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for c in value.chars() {
+        match (quote, c) {
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'' | '`') => quote = Some(c),
+            (None, '<' | '(' | '[' | '{') => depth += 1,
+            (None, '>' | ')' | ']' | '}') => depth -= 1,
+            (None, '|' | '&') if depth == 0 => return true,
+            _ => {}
+        }
+    }
+
+    false
+}
+
+/// Formats a tuple from the already formatted values of its elements. Optional elements
+/// are a union of every accepted length before typescript 3.0 and readonly tuples are
+/// mutable before typescript 3.4
+fn tuple(
+    typing: &Typing,
+    nx: NodeIndex,
+    node_value: &HashMap<NodeIndex, String>,
+    settings: &Settings,
+) -> String {
+    let elements: Vec<_> = typing
+        .children(nx)
+        .map(|n_nx| {
+            let (_, optional) = typing.graph[n_nx].as_tuple_element().unwrap();
+            (optional, &node_value[&n_nx])
+        })
+        .collect();
+
+    if !settings.typescript_at_least(3, 0) {
+        let required = elements.iter().filter(|(optional, _)| !optional).count();
+
+        return (required..=elements.len())
+            .map(|len| format!("[{}]", elements[..len].iter().map(|(_, v)| v).join(", ")))
+            .join(" | ");
+    }
+
+    let elements = elements
+        .into_iter()
+        .map(
+            |(optional, value)| match (optional, value.contains(" | ") || value.contains(" & ")) {
+                (true, true) => format!("({})?", value),
+                (true, false) => format!("{}?", value),
+                _ => value.clone(),
+            },
+        )
+        .join(", ");
+
+    match settings.readonly && settings.typescript_at_least(3, 4) {
+        true => format!("readonly [{}]", elements),
+        false => format!("[{}]", elements),
+    }
+}
+
+/// Typescript type of a literal, template literal types are strings before typescript 4.1
+fn literal(l: &Literal, settings: &Settings) -> String {
+    match l {
+        Literal::Template(_) if !settings.typescript_at_least(4, 1) => String::from("string"),
+        _ => l.to_string(&settings.string_delimiter),
+    }
+}
+
+/// Typescript type of a formatted string, branded with its format for the branded style.
+/// Integer strings are parsed as strings, so they don't follow the bigint style.
+fn string_format(format: &StringFormat, settings: &Settings) -> String {
+    match settings.format_style {
+        FormatStyle::Branded => branded(format, settings),
+        _ => String::from("string"),
+    }
+}
+
+/// Typescript type of numbers, integers out of the safe integer range follow the bigint
+/// style, `bigint` only exists since typescript 3.2
+fn number(range: &NumberRange, settings: &Settings) -> String {
+    if !range.is_unsafe() {
+        return String::from("number");
+    }
+
+    match settings.bigint_style {
+        BigIntStyle::BigInt if settings.typescript_at_least(3, 2) => String::from("bigint"),
+        BigIntStyle::String => String::from("string"),
+        BigIntStyle::Branded => branded(&StringFormat::Int64, settings),
+        _ => String::from("number"),
+    }
+}
+
+fn branded(format: &StringFormat, settings: &Settings) -> String {
+    format!(
+        "string & {{ readonly __format: {0}{1}{0} }}",
+        settings.string_delimiter,
+        format.name()
+    )
+}
+
+/// Entry of an interface from the formatted value of an object entry without `null`
+fn interface_entry(
+    typing: &Typing,
+    nx: NodeIndex,
+    value: String,
+    settings: &Settings,
+) -> InterfaceEntry {
+    let (key, optional) = typing.graph[nx].as_object_entry().unwrap();
+    let nullable = typing
+        .children(nx)
+        .any(|n_nx| typing.graph[n_nx] == TypingNode::Null);
+    let (value, optional) = settings.null_style.apply(&value, optional, nullable);
+
+    InterfaceEntry {
+        key: String::from(key),
+        optional,
+        value,
+        comment: entry_comment(typing, nx, settings),
+    }
+}
+
+/// JSDoc tags of an object entry for the JSDoc style, `@format` for formatted strings,
+/// `@type integer` for integers and their `@minimum` and `@maximum` with the bounds option
+fn entry_comment(typing: &Typing, nx: NodeIndex, settings: &Settings) -> Option<String> {
+    if settings.format_style != FormatStyle::JsDoc {
+        return None;
+    }
+
+    let tags = typing
+        .children(nx)
+        .flat_map(|n_nx| {
+            let mut tags = vec![];
+            match &typing.graph[n_nx] {
+                TypingNode::Format(f) => tags.push(format!("@format {}", f.name())),
+                TypingNode::Number(range) => {
+                    if range.is_integer() {
+                        tags.push(String::from("@type integer"));
+                    }
+                    if settings.number_bounds {
+                        tags.push(format!("@minimum {}", range.bound(range.min)));
+                        tags.push(format!("@maximum {}", range.bound(range.max)));
+                    }
+                }
+                _ => {}
+            }
+
+            tags
+        })
+        .join(" ");
+
+    match tags.len() {
+        0 => None,
+        _ => Some(tags),
+    }
+}
+
+/// Top type, `unknown` only exists since typescript 3.0
+fn unknown(settings: &Settings) -> &'static str {
+    if settings.typescript_at_least(3, 0) {
+        "unknown"
+    } else {
+        "any"
+    }
+}
+
+/// Literal children of `nx` declared together as an enumeration along with their values,
+/// `None` when literals are declared as unions or there are fewer than two of them
+fn literal_set(
+    typing: &Typing,
+    nx: NodeIndex,
+    settings: &Settings,
+) -> Option<(Vec<NodeIndex>, Vec<Literal>)> {
+    if settings.literal_style == LiteralStyle::Union {
+        return None;
+    }
+
+    let (literals, values): (Vec<_>, Vec<_>) = typing
+        .children(nx)
+        .filter_map(|n_nx| match &typing.graph[n_nx] {
+            TypingNode::Literal(l @ (Literal::String(_) | Literal::Number(_))) => {
+                Some((n_nx, l.clone()))
+            }
+            _ => None,
+        })
+        .unzip();
+
+    match literals.len() {
+        0 | 1 => None,
+        _ => Some((literals, values)),
+    }
+}
+
+/// Name of the enumeration of the literals of `nx` within its object, the PascalCase key
+/// followed by the position for tuple elements
+fn enumeration_key(typing: &Typing, nx: NodeIndex) -> String {
+    match &typing.graph[nx] {
+        TypingNode::ObjectEntry { key, .. }
+        | TypingNode::Array { key, .. }
+        | TypingNode::Record { key, .. }
+        | TypingNode::Tuple { key, .. } => key.to_case(Case::Pascal),
+        TypingNode::TupleElement { index, .. } => {
+            let tuple = typing
+                .graph
+                .neighbors_directed(nx, Direction::Incoming)
+                .next()
+                .unwrap();
+
+            format!("{}Element{}", enumeration_key(typing, tuple), index)
+        }
+        _ => String::new(),
+    }
+}
+
+impl Tree {
+    /// Whether a union is small enough to be written inline rather than as an alias
+    fn inline(values: &[String], settings: &Settings) -> bool {
+        values.len() <= settings.inline_union_max_members
+            && values.join(" | ").len() <= settings.inline_union_max_length
+    }
+
+    /// Type of `n_nx` as referenced from an entry of `object_node`
+    ///
+    /// Objects are nested in the namespace of their first parent, any other parent refers to
+    /// them by their full path.
+    fn reference(
+        paths: &HashMap<NodeIndex, TypePath>,
+        node_namespace: &mut HashMap<NodeIndex, Namespace>,
+        object_node: NodeIndex,
+        value: String,
+        n_nx: NodeIndex,
+        settings: &Settings,
+    ) -> String {
+        let path = match paths.get(&n_nx) {
+            Some(path) => path,
+            None => return value,
+        };
+
+        let (_, parent_segments) = path.segments.split_last().unwrap();
+        if parent_segments != paths[&object_node].segments.as_slice() {
+            return path.path();
+        }
+
+        let object_name = &paths[&object_node].segments.last().unwrap();
+        if let Some(namespace) = node_namespace.remove(&n_nx) {
+            node_namespace
+                .entry(object_node)
+                .or_insert_with(|| Namespace::new(object_name))
+                .entries
+                .push(NamespaceEntry::new(
+                    &value,
+                    NamespaceEntryValue::Namespace(namespace.to_string(settings)),
+                ));
+        }
+
+        format!("{}.{}", object_name, value)
+    }
+
+    /// Declares the literal set of `nx` in the namespace of `object_node`, returns the nodes
+    /// of the literals and the reference to their enumeration
+    fn enumeration(
+        typing: &Typing,
+        nx: NodeIndex,
+        object_node: NodeIndex,
+        node_namespace: &mut HashMap<NodeIndex, Namespace>,
+        settings: &Settings,
+    ) -> Option<(Vec<NodeIndex>, String)> {
+        let (literals, values) = literal_set(typing, nx, settings)?;
+
+        let object_name = typing.graph[object_node].as_object().unwrap();
+        let namespace = node_namespace
+            .entry(object_node)
+            .or_insert_with(|| Namespace::new(object_name));
+
+        // Objects of the entries are nested in the same namespace once declared
+        let objects: HashSet<_> = referenced_objects(typing, object_node)
+            .into_iter()
+            .filter_map(|o_nx| typing.graph[o_nx].as_object())
+            .collect();
+        let key = enumeration_key(typing, nx);
+        let key = (1..)
+            .map(|count| match count {
+                1 => key.clone(),
+                _ => format!("{}{}", key, count),
+            })
+            .find(|key| {
+                !objects.contains(key.as_str()) && namespace.entries.iter().all(|e| &e.key != key)
+            })
+            .unwrap();
+
+        let enumeration = Enumeration::new(&key, values);
+        namespace.entries.push(NamespaceEntry::new(
+            &key,
+            NamespaceEntryValue::Enumeration(enumeration.to_string(settings)),
+        ));
+
+        Some((literals, format!("{}.{}", object_name, key)))
     }
 }
 
 impl Strategy {
-    pub fn to_string(self, typing: &Typing) -> String {
+    pub fn to_string(self, typing: &Typing, settings: &Settings) -> String {
+        match self {
+            Self::Tree => Tree::fmt_typing(typing, settings),
+            Self::Family => Family::fmt_typing(typing, settings),
+            Self::JsonSchema => JsonSchema::fmt_typing(typing, settings),
+            Self::Rust => Rust::fmt_typing(typing, settings),
+            Self::Zod => Zod::fmt_typing(typing, settings),
+        }
+    }
+
+    /// Formats several root typings into a single output, each one is declared separately
+    pub fn to_string_all(self, typings: &[Typing], settings: &Settings) -> String {
+        match self {
+            Self::Tree => Tree::fmt_typings(typings, settings),
+            Self::Family => Family::fmt_typings(typings, settings),
+            Self::JsonSchema => JsonSchema::fmt_typings(typings, settings),
+            Self::Rust => Rust::fmt_typings(typings, settings),
+            Self::Zod => Zod::fmt_typings(typings, settings),
+        }
+    }
+
+    /// Formats several root typings into one module per top level declaration, `None` when
+    /// this strategy can't be split
+    pub fn to_modules(self, typings: &[Typing], settings: &Settings) -> Option<Vec<Module>> {
         match self {
-            Self::Tree => Tree::fmt_typing(typing),
-            Self::Family => Family::fmt_typing(typing),
+            Self::Tree => Tree::fmt_modules(typings, settings),
+            Self::Family => Family::fmt_modules(typings, settings),
+            Self::JsonSchema => JsonSchema::fmt_modules(typings, settings),
+            Self::Rust => Rust::fmt_modules(typings, settings),
+            Self::Zod => Zod::fmt_modules(typings, settings),
         }
     }
+
+    /// Extension of the files generated by this strategy, typescript declarations are
+    /// typescript files when literals are declared as values
+    pub fn extension(&self, settings: &Settings) -> &'static str {
+        match self {
+            Self::Tree | Self::Family if settings.literal_style.declares_values() => "ts",
+            Self::Tree | Self::Family => "d.ts",
+            Self::Zod => "ts",
+            Self::JsonSchema => "json",
+            Self::Rust => "rs",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ijson::ijson;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::NullStyle;
+
+    #[test]
+    fn family() {
+        let value = ijson!({
+            "id": 1,
+            "glossary": {
+                "title": "example glossary",
+                "GlossEntry": {
+                    "GlossDef": {
+                        "para": "A meta-markup language",
+                    },
+                },
+            },
+            "tags": [{ "name": "a" }],
+        });
+        let typing = Typing::from_items("All", value);
+
+        self::assert_eq!(
+            [
+                "export interface All {",
+                "    id: number;",
+                "    glossary: AllGlossary;",
+                "    tags: Array<AllTags>;",
+                "}",
+                "",
+                "export interface AllGlossary {",
+                "    title: string;",
+                "    GlossEntry: GlossaryGlossEntry;",
+                "}",
+                "",
+                "export interface GlossaryGlossEntry {",
+                "    GlossDef: GlossEntryGlossDef;",
+                "}",
+                "",
+                "export interface GlossEntryGlossDef {",
+                "    para: string;",
+                "}",
+                "",
+                "export interface AllTags {",
+                "    name: string;",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.as_string(Strategy::Family)
+        );
+    }
+
+    #[test]
+    fn family_name_collision() {
+        let value = ijson!({
+            "a": { "item": { "value": { "x": 1 } } },
+            "b": { "item": { "value": { "y": true } } },
+        });
+        let typing = Typing::from_items("All", value);
+        let output = typing.as_string(Strategy::Family);
+
+        assert!(output.contains("export interface AItemValue {"));
+        assert!(output.contains("export interface BItemValue {"));
+        assert!(output.contains("    value: AItemValue;"));
+        assert!(output.contains("    value: BItemValue;"));
+    }
+
+    #[test]
+    fn tree_deduplicated() {
+        let value = ijson!({
+            "a": { "address": { "street": "x" } },
+            "b": { "address": { "street": "y" } },
+        });
+        let mut typing = Typing::from_items("All", value);
+        typing.deduplicate();
+
+        self::assert_eq!(
+            [
+                "export interface All {",
+                "    a: All.A;",
+                "    b: All.A;",
+                "}",
+                "",
+                "export namespace All {",
+                "    export interface A {",
+                "        address: A.Address;",
+                "    }",
+                "",
+                "    export namespace A {",
+                "        export interface Address {",
+                "            street: string;",
+                "        }",
+                "    }",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.as_string(Strategy::Tree)
+        );
+    }
+
+    #[test]
+    fn tree_deduplicated_across_namespaces() {
+        let value = ijson!({
+            "a": { "address": { "street": "x" }, "id": 1 },
+            "b": { "location": { "street": "y" } },
+        });
+        let mut typing = Typing::from_items("All", value);
+        typing.deduplicate();
+        let output = typing.as_string(Strategy::Tree);
+
+        assert!(output.contains("        address: A.Address;"));
+        assert!(output.contains("        location: All.A.Address;"));
+        self::assert_eq!(output.matches("interface Address").count(), 1);
+    }
+
+    #[test]
+    fn tree_record() {
+        let value = ijson!({
+            "users": {
+                "u123": { "name": "a" },
+                "u456": { "name": "b" },
+                "u789": { "name": "c" },
+                "u1011": { "name": "d" },
+            },
+            "scores": {
+                "2021-10-03": 1,
+                "2021-10-04": 2,
+                "2021-10-05": 3,
+                "2021-10-06": 4,
+            },
+        });
+        let typing = Typing::from_items("All", value);
+
+        self::assert_eq!(
+            [
+                "export interface All {",
+                "    users: Record<string, All.Users>;",
+                "    scores: Record<string, number>;",
+                "}",
+                "",
+                "export namespace All {",
+                "    export interface Users {",
+                "        name: string;",
+                "    }",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.as_string(Strategy::Tree)
+        );
+    }
+
+    #[test]
+    fn tree_tuple() {
+        let value = ijson!([
+            {
+                "point": [1, "a", true],
+                "pairs": [[1, "a"], [2, "b", { "x": 1 }]],
+                "list": [1, 2, 3],
+            },
+            {
+                "point": [2, "b"],
+                "pairs": [],
+                "list": [4],
+            },
+        ]);
+        let typing = Typing::from_items("All", value);
+
+        self::assert_eq!(
+            [
+                "export interface All {",
+                "    point: [number, string, boolean?];",
+                "    pairs: Array<[number, string, All.PairsElement2?]>;",
+                "    list: Array<number>;",
+                "}",
+                "",
+                "export namespace All {",
+                "    export interface PairsElement2 {",
+                "        x: number;",
+                "    }",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.as_string(Strategy::Tree)
+        );
+    }
+
+    #[test]
+    fn family_modules() {
+        let value = ijson!({ "owner": { "address": { "city": "c" } }, "tags": ["t"] });
+        let typing = Typing::from_items("All", value);
+        let modules = Strategy::Family
+            .to_modules(&[typing], &Settings::default())
+            .unwrap();
+
+        self::assert_eq!(
+            modules
+                .iter()
+                .map(|m| format!("// {}.ts\n{}", m.name, m.content))
+                .join("\n"),
+            [
+                "// All.ts",
+                "import { AllOwner } from \"./AllOwner\";",
+                "",
+                "export interface All {",
+                "    owner: AllOwner;",
+                "    tags: Array<string>;",
+                "}",
+                "",
+                "// AllOwner.ts",
+                "import { OwnerAddress } from \"./OwnerAddress\";",
+                "",
+                "export interface AllOwner {",
+                "    address: OwnerAddress;",
+                "}",
+                "",
+                "// OwnerAddress.ts",
+                "export interface OwnerAddress {",
+                "    city: string;",
+                "}",
+                "",
+                "// index.ts",
+                "export * from \"./All\";",
+                "export * from \"./AllOwner\";",
+                "export * from \"./OwnerAddress\";",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn module_imports() {
+        let value = ijson!({
+            "owners": [{ "kind": "AllTag" }, { "kind": "AllTag" }],
+            "tag": { "x": 1 },
+        });
+        let typing = Typing::from_items("All", value);
+        let modules = Strategy::Family
+            .to_modules(&[typing], &Settings::default())
+            .unwrap();
+
+        let owners = modules.iter().find(|m| m.name == "AllOwners").unwrap();
+        self::assert_eq!(
+            owners.content,
+            [
+                "export interface AllOwners {",
+                "    kind: \"AllTag\";",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn family_roots() {
+        let typings = [
+            Typing::from_items("A", ijson!({ "owner": { "address": { "street": "a" } } })),
+            Typing::from_items("B", ijson!({ "owner": { "address": { "zip": 1 } } })),
+        ];
+
+        self::assert_eq!(
+            Strategy::Family.to_string_all(&typings, &Settings::default()),
+            [
+                "export interface A {",
+                "    owner: AOwner;",
+                "}",
+                "",
+                "export interface AOwner {",
+                "    address: AOwnerAddress;",
+                "}",
+                "",
+                "export interface AOwnerAddress {",
+                "    street: string;",
+                "}",
+                "",
+                "export interface B {",
+                "    owner: BOwner;",
+                "}",
+                "",
+                "export interface BOwner {",
+                "    address: BOwnerAddress;",
+                "}",
+                "",
+                "export interface BOwnerAddress {",
+                "    zip: number;",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+
+        let rust = Strategy::Rust.to_string_all(&typings, &Settings::default());
+        self::assert_eq!(rust.matches("pub struct AOwnerAddress ").count(), 1);
+        self::assert_eq!(rust.matches("pub struct BOwnerAddress ").count(), 1);
+    }
+
+    #[test]
+    fn tree_settings() {
+        let value = ijson!({ "kind": ["a", "a"], "owner": { "name": "n" } });
+        let typing = Typing::from_items("All", value);
+        let settings = Settings {
+            indentation: String::from("  "),
+            string_delimiter: String::from("'"),
+            ..Settings::default()
+        };
+
+        self::assert_eq!(
+            [
+                "export interface All {",
+                "  kind: Array<'a'>;",
+                "  owner: All.Owner;",
+                "}",
+                "",
+                "export namespace All {",
+                "  export interface Owner {",
+                "    name: string;",
+                "  }",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.render(&settings)
+        );
+    }
+
+    #[test]
+    fn typescript_versions() {
+        let value = ijson!([
+            {
+                "size": "10px",
+                "tags": [],
+                "point": [1, "a", true],
+                "scores": { "2021-10-03": 1, "2021-10-04": 2 },
+            },
+            {
+                "size": "10px",
+                "tags": [],
+                "point": [2, "b"],
+                "scores": { "2021-10-05": 3, "2021-10-06": 4 },
+            },
+        ]);
+        let typing = Typing::from_items("All", value);
+
+        let matrix = [
+            (
+                "latest",
+                [
+                    "size: `${number}px`;",
+                    "tags: Array<unknown>;",
+                    "point: [number, string, boolean?];",
+                    "scores: Record<string, number>;",
+                ],
+            ),
+            (
+                "4.0",
+                [
+                    "size: string;",
+                    "tags: Array<unknown>;",
+                    "point: [number, string, boolean?];",
+                    "scores: Record<string, number>;",
+                ],
+            ),
+            (
+                "2.9",
+                [
+                    "size: string;",
+                    "tags: Array<any>;",
+                    "point: [number, string] | [number, string, boolean];",
+                    "scores: Record<string, number>;",
+                ],
+            ),
+            (
+                "2.0",
+                [
+                    "size: string;",
+                    "tags: Array<any>;",
+                    "point: [number, string] | [number, string, boolean];",
+                    "scores: { [key: string]: number };",
+                ],
+            ),
+        ];
+
+        for (version, entries) in matrix {
+            let settings = Settings {
+                typescript_version: String::from(version),
+                strategy: Strategy::Family,
+                ..Settings::default()
+            };
+            let entries = entries.iter().map(|e| format!("    {}", e)).join("\n");
+
+            self::assert_eq!(
+                format!("export interface All {{\n{}\n}}\n", entries),
+                typing.render(&settings),
+                "typescript {}",
+                version
+            );
+        }
+    }
+
+    /// Asserts that `typing` renders as an `All` interface with the entries of every row of
+    /// `matrix`, each row rendered with the settings made from its first element
+    fn assert_matrix<T: std::fmt::Debug, const N: usize>(
+        typing: &Typing,
+        matrix: &[(T, [&str; N])],
+        settings: impl Fn(&T) -> Settings,
+    ) {
+        for (row, entries) in matrix {
+            let entries = entries.iter().map(|e| format!("    {}", e)).join("\n");
+
+            self::assert_eq!(
+                format!("export interface All {{\n{}\n}}\n", entries),
+                typing.render(&settings(row)),
+                "{:?}",
+                row
+            );
+        }
+    }
+
+    #[test]
+    fn null_styles() {
+        let value = ijson!([
+            { "name": "a", "parent": "b", "count": 1 },
+            { "name": null, "parent": "c" },
+        ]);
+        let typing = Typing::from_items("All", value);
+        let matrix = [
+            (
+                NullStyle::Separate,
+                ["name: string | null;", "parent: string;", "count?: number;"],
+            ),
+            (
+                NullStyle::Optional,
+                ["name?: string;", "parent: string;", "count?: number;"],
+            ),
+            (
+                NullStyle::Null,
+                [
+                    "name: string | null;",
+                    "parent: string;",
+                    "count: number | null;",
+                ],
+            ),
+            (
+                NullStyle::OptionalNull,
+                [
+                    "name?: string | null;",
+                    "parent: string;",
+                    "count?: number | null;",
+                ],
+            ),
+            (
+                NullStyle::Undefined,
+                [
+                    "name: string | undefined;",
+                    "parent: string;",
+                    "count: number | undefined;",
+                ],
+            ),
+        ];
+
+        assert_matrix(&typing, &matrix, |null_style| Settings {
+            null_style: null_style.clone(),
+            ..Settings::default()
+        });
+    }
+
+    #[test]
+    fn inline_unions() {
+        let value = ijson!([
+            { "id": 1, "tags": ["a", 1] },
+            { "id": "x", "tags": [true] },
+        ]);
+        let typing = Typing::from_items("All", value);
+        let settings = Settings {
+            inline_union_max_members: 3,
+            inline_union_max_length: 16,
+            ..Settings::default()
+        };
+
+        self::assert_eq!(
+            [
+                "export interface All {",
+                "    id: number | string;",
+                "    tags: Array<All.Tags>;",
+                "}",
+                "",
+                "export namespace All {",
+                "    export type Tags = boolean",
+                "        | number",
+                "        | string;",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.render(&settings)
+        );
+    }
+
+    #[test]
+    fn readonly() {
+        let value = ijson!({
+            "tags": ["a", "b"],
+            "scores": { "u10": 1, "u20": 2, "u30": 3, "u40": 4 },
+            "points": [[1, "a"], [2, "b"]],
+        });
+        let typing = Typing::from_items("All", vec![value]);
+        let matrix = [
+            (
+                "latest",
+                [
+                    "readonly tags: ReadonlyArray<string>;",
+                    "readonly scores: Readonly<Record<string, number>>;",
+                    "readonly points: ReadonlyArray<readonly [number, string]>;",
+                ],
+            ),
+            (
+                "2.0.0",
+                [
+                    "readonly tags: ReadonlyArray<string>;",
+                    "readonly scores: { readonly [key: string]: number };",
+                    "readonly points: ReadonlyArray<[number, string]>;",
+                ],
+            ),
+        ];
+
+        assert_matrix(&typing, &matrix, |version| Settings {
+            typescript_version: String::from(*version),
+            strategy: Strategy::Family,
+            readonly: true,
+            ..Settings::default()
+        });
+    }
+
+    #[test]
+    fn array_syntaxes() {
+        let value = ijson!([
+            { "tags": ["a"], "ids": [1, "a"], "matrix": [[1, 2]], "scores": [{ "u10": 1, "u20": 2 }] },
+            { "tags": ["b"], "ids": ["b", 2], "matrix": [[3]], "scores": [{ "u30": 3, "u40": 4 }] },
+        ]);
+        let typing = Typing::from_items("All", value);
+        let matrix = [
+            (
+                ArraySyntax::Generic,
+                [
+                    "tags: Array<string>;",
+                    "ids: Array<number | string>;",
+                    "matrix: Array<Array<number>>;",
+                    "scores: Array<Record<string, number>>;",
+                ],
+            ),
+            (
+                ArraySyntax::Array,
+                [
+                    "tags: string[];",
+                    "ids: (number | string)[];",
+                    "matrix: number[][];",
+                    "scores: Record<string, number>[];",
+                ],
+            ),
+            (
+                ArraySyntax::ArraySimple,
+                [
+                    "tags: string[];",
+                    "ids: Array<number | string>;",
+                    "matrix: number[][];",
+                    "scores: Array<Record<string, number>>;",
+                ],
+            ),
+        ];
+
+        assert_matrix(&typing, &matrix, |array_syntax| Settings {
+            strategy: Strategy::Family,
+            array_syntax: array_syntax.clone(),
+            ..Settings::default()
+        });
+
+        let settings = Settings {
+            array_syntax: ArraySyntax::Array,
+            readonly: true,
+            ..Settings::default()
+        };
+        self::assert_eq!("readonly string[]", array("string", true, &settings));
+        self::assert_eq!(
+            "readonly (readonly [number])[]",
+            array("readonly [number]", true, &settings)
+        );
+        self::assert_eq!("\"a | b\"[]", array("\"a | b\"", false, &settings));
+    }
+
+    #[test]
+    fn index_signatures() {
+        let value = ijson!({ "scores": { "u10": 1, "u20": 2, "u30": 3, "u40": 4 } });
+        let typing = Typing::from_items("All", vec![value]);
+        let settings = Settings {
+            index_signatures: true,
+            strategy: Strategy::Family,
+            ..Settings::default()
+        };
+
+        self::assert_eq!(
+            "export interface All {\n    scores: { [key: string]: number };\n}\n",
+            typing.render(&settings)
+        );
+    }
+
+    #[test]
+    fn literal_styles() {
+        let value = ijson!([
+            { "status": "active", "owner": { "role": "admin" } },
+            { "status": "in-progress", "owner": { "role": "admin" } },
+            { "status": "active", "owner": { "role": "user" } },
+            { "status": null, "owner": { "role": "user" } },
+        ]);
+        let typing = Typing::from_items("All", value);
+        let settings = Settings {
+            literal_style: LiteralStyle::Enum,
+            strategy: Strategy::Family,
+            ..Settings::default()
+        };
+
+        self::assert_eq!(
+            [
+                "export interface All {",
+                "    status: AllStatus | null;",
+                "    owner: AllOwner;",
+                "}",
+                "",
+                "export enum AllStatus {",
+                "    Active = \"active\",",
+                "    InProgress = \"in-progress\",",
+                "}",
+                "",
+                "export interface AllOwner {",
+                "    role: AllOwnerRole;",
+                "}",
+                "",
+                "export enum AllOwnerRole {",
+                "    Admin = \"admin\",",
+                "    User = \"user\",",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.render(&settings)
+        );
+
+        let settings = Settings {
+            literal_style: LiteralStyle::Object,
+            strategy: Strategy::Tree,
+            ..Settings::default()
+        };
+
+        self::assert_eq!(
+            [
+                "export interface All {",
+                "    status: All.Status | null;",
+                "    owner: All.Owner;",
+                "}",
+                "",
+                "export namespace All {",
+                "    export interface Owner {",
+                "        role: Owner.Role;",
+                "    }",
+                "",
+                "    export namespace Owner {",
+                "        export const Role = {",
+                "            Admin: \"admin\",",
+                "            User: \"user\",",
+                "        } as const;",
+                "        export type Role = typeof Role[keyof typeof Role];",
+                "    }",
+                "",
+                "    export const Status = {",
+                "        Active: \"active\",",
+                "        InProgress: \"in-progress\",",
+                "    } as const;",
+                "    export type Status = typeof Status[keyof typeof Status];",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.render(&settings)
+        );
+    }
+
+    #[test]
+    fn literal_style_objects() {
+        let value = ijson!([
+            { "owner": "a" },
+            { "owner": "a" },
+            { "owner": { "x": 1 } },
+            { "owner": "b" },
+        ]);
+        let typing = Typing::from_items("All", value);
+        let settings = Settings {
+            literal_style: LiteralStyle::Enum,
+            ..Settings::default()
+        };
+
+        self::assert_eq!(
+            [
+                "export interface All {",
+                "    owner: All.Owner2 | All.Owner;",
+                "}",
+                "",
+                "export namespace All {",
+                "    export interface Owner {",
+                "        x: number;",
+                "    }",
+                "",
+                "    export enum Owner2 {",
+                "        A = \"a\",",
+                "        B = \"b\",",
+                "    }",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.render(&settings)
+        );
+    }
+
+    #[test]
+    fn extension() {
+        let settings = |literal_style| Settings {
+            literal_style,
+            ..Settings::default()
+        };
+
+        self::assert_eq!(
+            Strategy::Tree.extension(&settings(LiteralStyle::Union)),
+            "d.ts"
+        );
+        self::assert_eq!(
+            Strategy::Family.extension(&settings(LiteralStyle::ConstEnum)),
+            "d.ts"
+        );
+        self::assert_eq!(
+            Strategy::Tree.extension(&settings(LiteralStyle::Enum)),
+            "ts"
+        );
+        self::assert_eq!(
+            Strategy::Family.extension(&settings(LiteralStyle::Object)),
+            "ts"
+        );
+        self::assert_eq!(Strategy::Zod.extension(&settings(LiteralStyle::Enum)), "ts");
+    }
+
+    #[test]
+    fn format_styles() {
+        let value = ijson!([
+            { "url": "https://example.com/a", "ips": ["10.0.0.1"], "rank": 1 },
+            { "url": "https://example.com/b", "ips": ["10.0.0.2"], "rank": 2 },
+        ]);
+        let formats = Settings {
+            formats: true,
+            ..Settings::default()
+        };
+        let typing = Typing::from_items_with_settings("All", value, &formats);
+        let settings = Settings {
+            format_style: FormatStyle::Branded,
+            strategy: Strategy::Family,
+            ..Settings::default()
+        };
+
+        self::assert_eq!(
+            [
+                "export interface All {",
+                "    url: string & { readonly __format: \"uri\" };",
+                "    ips: Array<string & { readonly __format: \"ipv4\" }>;",
+                "    rank: number;",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.render(&settings)
+        );
+
+        let settings = Settings {
+            format_style: FormatStyle::JsDoc,
+            number_bounds: true,
+            strategy: Strategy::Family,
+            ..Settings::default()
+        };
+
+        self::assert_eq!(
+            [
+                "export interface All {",
+                "    /** @format uri */",
+                "    url: string;",
+                "    ips: Array<string>;",
+                "    /** @type integer @minimum 1 @maximum 2 */",
+                "    rank: number;",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.render(&settings)
+        );
+    }
+
+    #[test]
+    fn bigint_styles() {
+        let value = ijson!([
+            { "id": 9_007_199_254_740_993u64, "ref": "1445185496281014272", "count": 1 },
+            { "id": 1u64, "ref": "1445185496281014273", "count": 2 },
+        ]);
+        let formats = Settings {
+            formats: true,
+            ..Settings::default()
+        };
+        let typing = Typing::from_items_with_settings("All", value, &formats);
+        let render = |bigint_style, typescript_version: &str| {
+            let settings = Settings {
+                bigint_style,
+                typescript_version: String::from(typescript_version),
+                strategy: Strategy::Family,
+                ..Settings::default()
+            };
+
+            typing.render(&settings)
+        };
+        // Integer strings stay strings whichever way integers are parsed
+        let interface = |id: &str| {
+            [
+                "export interface All {",
+                &format!("    id: {};", id),
+                "    ref: string;",
+                "    count: number;",
+                "}",
+                "",
+            ]
+            .join("\n")
+        };
+        let branded = "string & { readonly __format: \"int64\" }";
+
+        self::assert_eq!(interface("number"), render(BigIntStyle::Number, "latest"));
+        self::assert_eq!(interface("bigint"), render(BigIntStyle::BigInt, "latest"));
+        self::assert_eq!(interface("number"), render(BigIntStyle::BigInt, "3.1"));
+        self::assert_eq!(interface("string"), render(BigIntStyle::String, "latest"));
+        self::assert_eq!(interface(branded), render(BigIntStyle::Branded, "latest"));
+    }
 }
@@ -0,0 +1,433 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use petgraph::{
+    graph::NodeIndex,
+    visit::{Reversed, Topo},
+};
+use serde::Serialize;
+use serde_json::{json, ser::PrettyFormatter, Map, Serializer, Value};
+
+use super::{Family, TypingStrategy};
+use crate::{regex, Literal, NumberKind, NumberRange, Settings, StringFormat, Typing, TypingNode};
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+pub(super) struct JsonSchema;
+impl TypingStrategy for JsonSchema {
+    fn fmt_typing(typing: &Typing, settings: &Settings) -> String {
+        let names = Family::object_names(std::slice::from_ref(typing));
+        let mut schema = Map::new();
+        schema.insert(String::from("$schema"), json!(DRAFT));
+        schema.extend(JsonSchema::schema(typing, &names[0], settings));
+
+        format!("{}\n", to_string_pretty(&Value::Object(schema), settings))
+    }
+
+    /// Every root typing becomes a definition of a single schema, along with their nested
+    /// definitions
+    fn fmt_typings(typings: &[Typing], settings: &Settings) -> String {
+        if let [typing] = typings {
+            return JsonSchema::fmt_typing(typing, settings);
+        }
+
+        let names = Family::object_names(typings);
+        let mut defs = Map::new();
+        for (typing, names) in typings.iter().zip(&names) {
+            let mut schema = JsonSchema::schema(typing, names, settings);
+            if let Some(Value::Object(nested_defs)) = schema.remove("$defs") {
+                defs.extend(nested_defs);
+            }
+
+            let title = schema["title"].as_str().unwrap_or_default().to_string();
+            let mut schema = Value::Object(schema);
+            root_refs(&mut schema, &format!("#/$defs/{}", title));
+            defs.insert(title, schema);
+        }
+
+        let schema = json!({ "$schema": DRAFT, "$defs": defs });
+
+        format!("{}\n", to_string_pretty(&schema, settings))
+    }
+}
+
+impl JsonSchema {
+    /// Schema of the root object of a typing with its nested objects in `$defs`, named
+    /// after `names`
+    fn schema(
+        typing: &Typing,
+        names: &HashMap<NodeIndex, String>,
+        settings: &Settings,
+    ) -> Map<String, Value> {
+        let graph = &typing.graph;
+        let root = NodeIndex::new(0);
+
+        let graph_adaptor = Reversed(graph);
+        let mut topo = Topo::new(&graph_adaptor);
+
+        let mut node_schema: HashMap<NodeIndex, Value> = HashMap::new();
+        let mut defs = HashMap::new();
+        while let Some(nx) = topo.next(&graph_adaptor) {
+            let schema = match &graph[nx] {
+                TypingNode::Null => json!({ "type": "null" }),
+                TypingNode::Boolean => json!({ "type": "boolean" }),
+                TypingNode::Number(range) => number(range, settings),
+                TypingNode::String => json!({ "type": "string" }),
+                TypingNode::Format(StringFormat::HexColor) => {
+                    json!({ "type": "string", "pattern": regex::HEX_COLOR.as_str() })
+                }
+                TypingNode::Format(StringFormat::Base64) => {
+                    json!({ "type": "string", "contentEncoding": "base64" })
+                }
+                TypingNode::Format(f) => json!({ "type": "string", "format": f.name() }),
+                TypingNode::Literal(Literal::Template(t)) => {
+                    json!({ "type": "string", "pattern": template_pattern(t) })
+                }
+                TypingNode::Literal(Literal::String(s)) => json!({ "const": s }),
+                TypingNode::Literal(Literal::Number(n)) => json!({ "const": n }),
+                TypingNode::Array { .. } => {
+                    json!({ "type": "array", "items": union(typing, nx, &node_schema) })
+                }
+                TypingNode::Record { .. } => {
+                    json!({
+                        "type": "object",
+                        "additionalProperties": union(typing, nx, &node_schema),
+                    })
+                }
+                TypingNode::Tuple { .. } => {
+                    let elements: Vec<_> = typing.children(nx).collect();
+                    let required = elements
+                        .iter()
+                        .filter(|n_nx| !graph[**n_nx].as_tuple_element().unwrap().1)
+                        .count();
+
+                    json!({
+                        "type": "array",
+                        "prefixItems": elements.iter().map(|n_nx| &node_schema[n_nx]).collect_vec(),
+                        "minItems": required,
+                        "maxItems": elements.len(),
+                    })
+                }
+                TypingNode::ObjectEntry { .. } | TypingNode::TupleElement { .. } => {
+                    union(typing, nx, &node_schema)
+                }
+                TypingNode::Object(_) if typing.is_union(nx) => {
+                    let variants: Vec<_> = typing
+                        .children(nx)
+                        .map(|n_nx| &node_schema[&n_nx])
+                        .collect();
+                    defs.insert(nx, json!({ "oneOf": variants }));
+
+                    json!({ "$ref": "#" })
+                }
+                TypingNode::Object(_) => {
+                    let mut properties = Map::new();
+                    let mut required = vec![];
+                    for n_nx in typing.children(nx) {
+                        let (key, optional) = graph[n_nx].as_object_entry().unwrap();
+                        properties.insert(String::from(key), node_schema[&n_nx].clone());
+
+                        if !optional {
+                            required.push(key);
+                        }
+                    }
+
+                    defs.insert(
+                        nx,
+                        json!({
+                            "type": "object",
+                            "properties": properties,
+                            "required": required,
+                        }),
+                    );
+
+                    if nx == root {
+                        json!({ "$ref": "#" })
+                    } else {
+                        json!({ "$ref": format!("#/$defs/{}", names[&nx]) })
+                    }
+                }
+            };
+
+            node_schema.insert(nx, schema);
+        }
+
+        let mut schema = Map::new();
+        schema.insert(String::from("title"), json!(names[&root]));
+        if let Some(Value::Object(root_schema)) = defs.remove(&root) {
+            schema.extend(root_schema);
+        }
+
+        let nested_defs: Map<_, _> = typing
+            .object_paths()
+            .into_iter()
+            .filter_map(|(nx, _)| defs.remove(&nx).map(|def| (names[&nx].clone(), def)))
+            .collect();
+        if !nested_defs.is_empty() {
+            schema.insert(String::from("$defs"), Value::Object(nested_defs));
+        }
+
+        schema
+    }
+}
+
+/// Points the references to the document root to `reference` instead
+fn root_refs(schema: &mut Value, reference: &str) {
+    match schema {
+        Value::Object(map) => {
+            if map.get("$ref").and_then(Value::as_str) == Some("#") {
+                map.insert(String::from("$ref"), json!(reference));
+            }
+            map.values_mut().for_each(|v| root_refs(v, reference));
+        }
+        Value::Array(values) => values.iter_mut().for_each(|v| root_refs(v, reference)),
+        _ => {}
+    }
+}
+
+/// Schema of a number, integers are typed as such with the `int32` or `int64` format when
+/// they fit, along with the observed bounds with the bounds option
+fn number(range: &NumberRange, settings: &Settings) -> Value {
+    let mut schema = match range.kind {
+        NumberKind::I32 => json!({ "type": "integer", "format": "int32" }),
+        NumberKind::I64 => json!({ "type": "integer", "format": "int64" }),
+        NumberKind::U64 => json!({ "type": "integer" }),
+        NumberKind::Float => json!({ "type": "number" }),
+    };
+    if settings.number_bounds {
+        schema["minimum"] = json!(range.bound(range.min));
+        schema["maximum"] = json!(range.bound(range.max));
+    }
+
+    schema
+}
+
+/// Schema accepting any of the children of `nx`, literals are grouped in a single `enum`
+fn union(typing: &Typing, nx: NodeIndex, node_schema: &HashMap<NodeIndex, Value>) -> Value {
+    let (literals, mut schemas): (Vec<_>, Vec<_>) = typing
+        .children(nx)
+        .filter_map(|n_nx| node_schema.get(&n_nx))
+        .partition(|schema| schema.get("const").is_some());
+
+    let literals: Vec<_> = literals.into_iter().map(|l| l["const"].clone()).collect();
+    let enum_schema = match literals.len() {
+        0 => None,
+        1 => Some(json!({ "const": literals[0] })),
+        _ => Some(json!({ "enum": literals })),
+    };
+    if let Some(enum_schema) = &enum_schema {
+        schemas.push(enum_schema);
+    }
+
+    match schemas.len() {
+        0 => json!({}),
+        1 => schemas[0].clone(),
+        _ => json!({ "anyOf": schemas }),
+    }
+}
+
+/// Regex pattern matching the strings accepted by a typescript template literal
+pub(super) fn template_pattern(template: &str) -> String {
+    let pattern = template
+        .split("${")
+        .enumerate()
+        .map(|(i, part)| match i {
+            0 => escape(part),
+            _ => {
+                let (placeholder, rest) = part.split_once('}').unwrap_or((part, ""));
+                let placeholder = match placeholder {
+                    "bigint" => r"-?\d+n",
+                    _ => r"-?\d+(\.\d+)?",
+                };
+
+                format!("{}{}", placeholder, escape(rest))
+            }
+        })
+        .join("");
+
+    format!("^{}$", pattern)
+}
+
+fn escape(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '\\' | '.' | '+' | '*' | '?' | '(' | ')' | '|' | '[' | ']' | '{' | '}' | '^' | '$' => {
+                format!("\\{}", c)
+            }
+            _ => c.to_string(),
+        })
+        .collect()
+}
+
+fn to_string_pretty(value: &Value, settings: &Settings) -> String {
+    let formatter = PrettyFormatter::with_indent(settings.indentation.as_bytes());
+
+    let mut buffer = vec![];
+    let mut serializer = Serializer::with_formatter(&mut buffer, formatter);
+    value.serialize(&mut serializer).unwrap();
+
+    String::from_utf8(buffer).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use ijson::ijson;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use crate::{strategy::Strategy, Settings, Typing};
+
+    #[test]
+    fn json_schema() {
+        let value = ijson!([
+            {
+                "id": 1,
+                "kind": "a",
+                "size": "10px",
+                "tags": ["x"],
+                "owner": { "name": "n" },
+            },
+            {
+                "id": 2,
+                "kind": "a",
+                "size": "10px",
+                "tags": [],
+                "owner": null,
+                "extra": true,
+            },
+        ]);
+        let typing = Typing::from_items("All", value);
+        let schema: serde_json::Value =
+            serde_json::from_str(&typing.as_string(Strategy::JsonSchema)).unwrap();
+
+        self::assert_eq!(
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "title": "All",
+                "type": "object",
+                "properties": {
+                    "id": { "type": "integer", "format": "int32" },
+                    "kind": { "const": "a" },
+                    "size": { "type": "string", "pattern": r"^-?\d+(\.\d+)?px$" },
+                    "tags": { "type": "array", "items": { "type": "string" } },
+                    "owner": {
+                        "anyOf": [{ "type": "null" }, { "$ref": "#/$defs/AllOwner" }],
+                    },
+                    "extra": { "type": "boolean" },
+                },
+                "required": ["id", "kind", "size", "tags", "owner"],
+                "$defs": {
+                    "AllOwner": {
+                        "type": "object",
+                        "properties": { "name": { "type": "string" } },
+                        "required": ["name"],
+                    },
+                },
+            }),
+            schema
+        );
+    }
+
+    #[test]
+    fn json_schema_roots() {
+        let typings = [
+            Typing::from_items("User", ijson!({ "name": "n", "address": { "city": "c" } })),
+            Typing::from_items("Tag", ijson!({ "label": "l" })),
+        ];
+        let schema: serde_json::Value = serde_json::from_str(
+            &Strategy::JsonSchema.to_string_all(&typings, &Settings::default()),
+        )
+        .unwrap();
+
+        self::assert_eq!(
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "$defs": {
+                    "UserAddress": {
+                        "type": "object",
+                        "properties": { "city": { "type": "string" } },
+                        "required": ["city"],
+                    },
+                    "User": {
+                        "title": "User",
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "address": { "$ref": "#/$defs/UserAddress" },
+                        },
+                        "required": ["name", "address"],
+                    },
+                    "Tag": {
+                        "title": "Tag",
+                        "type": "object",
+                        "properties": { "label": { "type": "string" } },
+                        "required": ["label"],
+                    },
+                },
+            }),
+            schema
+        );
+    }
+
+    #[test]
+    fn string_formats() {
+        let value = ijson!([
+            { "id": "123e4567-e89b-12d3-a456-426614174000", "color": "#fff", "at": "2021-10-03" },
+            { "id": "123e4567-e89b-12d3-a456-426614174001", "color": "#000", "at": "2021-10-04" },
+        ]);
+        let settings = Settings {
+            formats: true,
+            ..Settings::default()
+        };
+        let typing = Typing::from_items_with_settings("All", value, &settings);
+        let schema: serde_json::Value =
+            serde_json::from_str(&typing.as_string(Strategy::JsonSchema)).unwrap();
+
+        self::assert_eq!(
+            json!({
+                "id": { "type": "string", "format": "uuid" },
+                "color": {
+                    "type": "string",
+                    "pattern": r"^#(?:[\da-fA-F]{3,4}|[\da-fA-F]{6}|[\da-fA-F]{8})$",
+                },
+                "at": { "type": "string", "format": "date" },
+            }),
+            schema["properties"]
+        );
+    }
+
+    #[test]
+    fn number_kinds() {
+        let mut typing = Typing::from_items(
+            "All",
+            ijson!([{ "count": 3, "size": 5_000_000_000i64, "ratio": 0.5 }]),
+        );
+        typing.merge(&Typing::from_items(
+            "All",
+            ijson!([{ "count": -1, "size": 1, "ratio": 2 }]),
+        ));
+        let settings = Settings {
+            number_bounds: true,
+            ..Settings::default()
+        };
+        let schema: serde_json::Value =
+            serde_json::from_str(&Strategy::JsonSchema.to_string(&typing, &settings)).unwrap();
+
+        self::assert_eq!(
+            json!({
+                "count": { "type": "integer", "format": "int32", "minimum": -1, "maximum": 3 },
+                "size": { "type": "integer", "format": "int64", "minimum": 1, "maximum": 5_000_000_000i64 },
+                "ratio": { "type": "number", "minimum": 0.5, "maximum": 2.0 },
+            }),
+            schema["properties"]
+        );
+    }
+
+    #[test]
+    fn template_pattern() {
+        self::assert_eq!(
+            super::template_pattern("v${number}.${bigint}"),
+            r"^v-?\d+(\.\d+)?\.-?\d+n$"
+        );
+    }
+}
@@ -0,0 +1,564 @@
+use std::collections::{HashMap, HashSet};
+
+use convert_case::{Case, Casing};
+use itertools::Itertools;
+use petgraph::{
+    graph::NodeIndex,
+    visit::{Reversed, Topo},
+    Direction,
+};
+
+use super::{Family, TypingStrategy};
+use crate::{Literal, NumberKind, Settings, StringFormat, Typing, TypingNode};
+
+const DERIVE: &str = "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]";
+
+/// Strict and reserved keywords, which can't be identifiers, `Self` included
+const KEYWORDS: [&str; 52] = [
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Rust representation of a typing node before unions are resolved
+#[derive(Debug, Clone, PartialEq)]
+enum RustType {
+    Null,
+    Literal(String),
+    Type { variant: String, ty: String },
+}
+
+#[derive(Default)]
+struct Definitions {
+    names: HashSet<String>,
+    enums: HashMap<NodeIndex, Vec<String>>,
+    hash_map: bool,
+}
+
+pub(super) struct Rust;
+impl TypingStrategy for Rust {
+    fn fmt_typing(typing: &Typing, settings: &Settings) -> String {
+        Rust::fmt_typings(std::slice::from_ref(typing), settings)
+    }
+
+    fn fmt_typings(typings: &[Typing], settings: &Settings) -> String {
+        let names = Family::object_names(typings);
+        let mut taken: HashSet<String> = names.iter().flat_map(|n| n.values().cloned()).collect();
+
+        let mut hash_map = false;
+        let items = typings
+            .iter()
+            .zip(&names)
+            .map(|(typing, names)| {
+                let (uses_hash_map, items) = Rust::items(typing, names, &mut taken, settings);
+                hash_map |= uses_hash_map;
+
+                items
+            })
+            .join("\n\n");
+
+        let mut header = String::new();
+        if hash_map {
+            header += "use std::collections::HashMap;\n\n";
+        }
+        header += "use serde::{Deserialize, Serialize};";
+
+        format!("{}\n\n{}\n", header, items)
+    }
+}
+
+impl Rust {
+    /// Structs and enums of a typing, along with whether they use a `HashMap`. Structs are
+    /// named after `names` and enums apart from the `taken` names.
+    fn items(
+        typing: &Typing,
+        names: &HashMap<NodeIndex, String>,
+        taken: &mut HashSet<String>,
+        settings: &Settings,
+    ) -> (bool, String) {
+        let graph = &typing.graph;
+        let indentation = &settings.indentation;
+
+        let mut definitions = Definitions {
+            names: std::mem::take(taken),
+            ..Definitions::default()
+        };
+
+        let graph_adaptor = Reversed(graph);
+        let mut topo = Topo::new(&graph_adaptor);
+
+        let mut node_type: HashMap<NodeIndex, RustType> = HashMap::new();
+        let mut structs = HashMap::new();
+        while let Some(nx) = topo.next(&graph_adaptor) {
+            let members = || -> Vec<RustType> {
+                typing
+                    .children(nx)
+                    .filter_map(|n_nx| node_type.get(&n_nx).cloned())
+                    .collect()
+            };
+
+            let rust_type = match &graph[nx] {
+                TypingNode::Null => RustType::Null,
+                TypingNode::Boolean => RustType::new("Bool", "bool"),
+                TypingNode::Number(range) => match range.kind {
+                    NumberKind::I32 => RustType::new("Integer", "i32"),
+                    NumberKind::I64 => RustType::new("Integer", "i64"),
+                    NumberKind::U64 => RustType::new("Integer", "u64"),
+                    NumberKind::Float => RustType::new("Number", "f64"),
+                },
+                TypingNode::Literal(Literal::Number(n)) if n.is_i64() => {
+                    RustType::new("Integer", "i64")
+                }
+                TypingNode::Literal(Literal::Number(n)) if n.is_u64() => {
+                    RustType::new("Integer", "u64")
+                }
+                TypingNode::Literal(Literal::Number(_)) => RustType::new("Number", "f64"),
+                TypingNode::Format(StringFormat::DateTime) if settings.dates => {
+                    RustType::new("DateTime", "chrono::DateTime<chrono::FixedOffset>")
+                }
+                TypingNode::Format(StringFormat::Date) if settings.dates => {
+                    RustType::new("Date", "chrono::NaiveDate")
+                }
+                TypingNode::String
+                | TypingNode::Format(_)
+                | TypingNode::Literal(Literal::Template(_)) => RustType::new("String", "String"),
+                TypingNode::Literal(Literal::String(s)) => RustType::Literal(s.clone()),
+                TypingNode::Array { object_node, key } => {
+                    let name = format!("{}{}Item", names[object_node], key.to_case(Case::Pascal));
+                    let item = definitions.union(members(), &name, *object_node, indentation);
+
+                    RustType::new("Array", &format!("Vec<{}>", item.ty()))
+                }
+                TypingNode::Record { object_node, key } => {
+                    let name = format!("{}{}Value", names[object_node], key.to_case(Case::Pascal));
+                    let value = definitions.union(members(), &name, *object_node, indentation);
+                    definitions.hash_map = true;
+
+                    RustType::new("Map", &format!("HashMap<String, {}>", value.ty()))
+                }
+                TypingNode::Tuple { .. } => {
+                    let elements: Vec<_> = typing.children(nx).collect();
+                    let optional = elements
+                        .iter()
+                        .any(|n_nx| graph[*n_nx].as_tuple_element().unwrap().1);
+
+                    // Serde tuples have a fixed length, shorter arrays can't be deserialized
+                    if optional {
+                        RustType::new("Array", "Vec<serde_json::Value>")
+                    } else {
+                        let ty = elements.iter().map(|n_nx| node_type[n_nx].ty()).join(", ");
+                        RustType::new("Tuple", &format!("({})", ty))
+                    }
+                }
+                TypingNode::TupleElement {
+                    index, object_node, ..
+                } => {
+                    let tuple = graph
+                        .neighbors_directed(nx, Direction::Incoming)
+                        .next()
+                        .unwrap();
+                    let key = match &graph[tuple] {
+                        TypingNode::Tuple { key, .. } => key.to_case(Case::Pascal),
+                        _ => String::new(),
+                    };
+                    let name = format!("{}{}{}", names[object_node], key, index);
+
+                    definitions.union(members(), &name, *object_node, indentation)
+                }
+                TypingNode::ObjectEntry {
+                    key, object_node, ..
+                } => {
+                    let name = format!("{}{}", names[object_node], key.to_case(Case::Pascal));
+
+                    definitions.union(members(), &name, *object_node, indentation)
+                }
+                TypingNode::Object(_) if typing.is_union(nx) => {
+                    let name = &names[&nx];
+                    let variants = typing
+                        .children(nx)
+                        .map(|n_nx| {
+                            let ty = node_type[&n_nx].ty();
+                            format!("{}{}({}),\n", indentation, ty, ty)
+                        })
+                        .join("");
+
+                    structs.insert(
+                        nx,
+                        format!(
+                            "{}\n#[serde(untagged)]\npub enum {} {{\n{}}}",
+                            DERIVE, name, variants
+                        ),
+                    );
+
+                    RustType::new(name, name)
+                }
+                TypingNode::Object(_) => {
+                    let name = &names[&nx];
+                    let mut seen = HashSet::new();
+                    let fields = typing
+                        .children(nx)
+                        .map(|n_nx| {
+                            let (key, optional) = graph[n_nx].as_object_entry().unwrap();
+                            let ty = node_type[&n_nx].ty();
+                            let ty = if optional && !ty.starts_with("Option<") {
+                                format!("Option<{}>", ty)
+                            } else {
+                                ty
+                            };
+
+                            let ident = field_identifier(key, &mut seen);
+                            let field = format!("{}pub {}: {},\n", indentation, ident, ty);
+                            if ident == key {
+                                field
+                            } else {
+                                format!("{}{}", rename(indentation, key), field)
+                            }
+                        })
+                        .join("");
+
+                    structs.insert(
+                        nx,
+                        format!("{}\npub struct {} {{\n{}}}", DERIVE, name, fields),
+                    );
+
+                    RustType::new(name, name)
+                }
+            };
+
+            node_type.insert(nx, rust_type);
+        }
+
+        let items = typing
+            .object_paths()
+            .into_iter()
+            .flat_map(|(nx, _)| {
+                let enums = definitions.enums.remove(&nx).unwrap_or_default();
+
+                structs.remove(&nx).into_iter().chain(enums)
+            })
+            .join("\n\n");
+
+        *taken = definitions.names;
+
+        (definitions.hash_map, items)
+    }
+}
+
+impl RustType {
+    fn new(variant: &str, ty: &str) -> Self {
+        Self::Type {
+            variant: String::from(variant),
+            ty: String::from(ty),
+        }
+    }
+
+    fn ty(&self) -> String {
+        match self {
+            Self::Null => String::from("Option<serde_json::Value>"),
+            Self::Literal(_) => String::from("String"),
+            Self::Type { ty, .. } => ty.clone(),
+        }
+    }
+
+    fn variant(&self) -> String {
+        match self {
+            Self::Type { variant, .. } => variant.clone(),
+            _ => String::from("Value"),
+        }
+    }
+}
+
+impl Definitions {
+    /// Resolves the members of a union into a single type, declaring an enum named `name`
+    /// when there is more than one, null members become an `Option`
+    fn union(
+        &mut self,
+        members: Vec<RustType>,
+        name: &str,
+        owner: NodeIndex,
+        indentation: &str,
+    ) -> RustType {
+        let nullable = members.contains(&RustType::Null);
+        let (literals, types): (Vec<_>, Vec<_>) = members
+            .into_iter()
+            .filter(|m| *m != RustType::Null)
+            .partition(|m| matches!(m, RustType::Literal(_)));
+
+        let inner = if types.is_empty() && !literals.is_empty() {
+            let variants = unique_variants(literals.iter().map(|l| match l {
+                RustType::Literal(s) => s.as_str(),
+                _ => unreachable!(),
+            }));
+            let body = variants
+                .into_iter()
+                .map(|(value, variant)| {
+                    format!(
+                        "{}{}{},\n",
+                        rename(indentation, value),
+                        indentation,
+                        variant
+                    )
+                })
+                .join("");
+
+            self.declare(owner, name, "", &body)
+        } else {
+            let types: Vec<_> = types
+                .into_iter()
+                .chain(literals.first().map(|_| RustType::new("String", "String")))
+                .unique_by(RustType::ty)
+                .collect();
+
+            match types.len() {
+                0 => return RustType::Null,
+                1 => types.into_iter().next().unwrap(),
+                _ => {
+                    let variants = types.iter().map(|t| match t {
+                        RustType::Type { variant, .. } => variant.as_str(),
+                        _ => unreachable!(),
+                    });
+                    let body = unique_variants(variants)
+                        .into_iter()
+                        .zip(&types)
+                        .map(|((_, variant), t)| {
+                            format!("{}{}({}),\n", indentation, variant, t.ty())
+                        })
+                        .join("");
+
+                    self.declare(owner, name, "#[serde(untagged)]\n", &body)
+                }
+            }
+        };
+
+        if nullable {
+            RustType::new(&inner.variant(), &format!("Option<{}>", inner.ty()))
+        } else {
+            inner
+        }
+    }
+
+    fn declare(&mut self, owner: NodeIndex, name: &str, attributes: &str, body: &str) -> RustType {
+        let mut unique_name = String::from(name);
+        while !self.names.insert(unique_name.clone()) {
+            unique_name += "Enum";
+        }
+
+        let definition = format!(
+            "{}\n{}pub enum {} {{\n{}}}",
+            DERIVE, attributes, unique_name, body
+        );
+        self.enums.entry(owner).or_default().push(definition);
+
+        RustType::new(&unique_name, &unique_name)
+    }
+}
+
+/// Pascal case variant names for each value, made unique and valid as identifiers
+fn unique_variants<'a, T: Iterator<Item = &'a str>>(values: T) -> Vec<(&'a str, String)> {
+    let mut seen = HashSet::new();
+
+    values
+        .map(|value| {
+            let mut variant = identifier(&value.to_case(Case::Pascal));
+            if variant.starts_with('_') {
+                variant.replace_range(..1, "V");
+            }
+            while !seen.insert(variant.clone()) {
+                variant += "_";
+            }
+
+            (value, variant)
+        })
+        .collect()
+}
+
+/// Snake case field name of a key, made unique among the `seen` fields of its struct
+fn field_identifier(key: &str, seen: &mut HashSet<String>) -> String {
+    let ident = match identifier(&key.to_case(Case::Snake)) {
+        ident if ident == "_" => String::from("field"),
+        ident => ident,
+    };
+
+    let mut unique = ident.clone();
+    let mut n = 1;
+    while !seen.insert(unique.clone()) {
+        n += 1;
+        unique = format!("{}_{}", ident, n);
+    }
+
+    unique
+}
+
+/// Replaces the characters that can't be part of an identifier, avoiding keywords
+fn identifier(s: &str) -> String {
+    let mut ident: String = s
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if KEYWORDS.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+
+    ident
+}
+
+fn rename(indentation: &str, key: &str) -> String {
+    format!("{}#[serde(rename = {:?})]\n", indentation, key)
+}
+
+#[cfg(test)]
+mod tests {
+    use ijson::ijson;
+    use pretty_assertions::assert_eq;
+
+    use crate::{strategy::Strategy, Typing};
+
+    #[test]
+    fn rust() {
+        let value = ijson!([
+            {
+                "id": 1,
+                "type": "admin",
+                "displayName": "a",
+                "tags": ["x"],
+                "owner": { "user-name": "n" },
+                "value": 1,
+            },
+            {
+                "id": 2,
+                "type": "user",
+                "displayName": "b",
+                "tags": [],
+                "owner": null,
+                "value": "1",
+                "extra": true,
+            },
+            {
+                "id": 3,
+                "type": "user",
+                "displayName": "c",
+                "tags": [],
+                "owner": null,
+                "value": 2,
+            },
+        ]);
+        let typing = Typing::from_items("All", value);
+
+        self::assert_eq!(
+            [
+                "use serde::{Deserialize, Serialize};",
+                "",
+                "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]",
+                "pub struct All {",
+                "    pub id: i32,",
+                "    #[serde(rename = \"type\")]",
+                "    pub type_: AllType,",
+                "    #[serde(rename = \"displayName\")]",
+                "    pub display_name: String,",
+                "    pub tags: Vec<String>,",
+                "    pub owner: Option<AllOwner>,",
+                "    pub value: AllValue,",
+                "    pub extra: Option<bool>,",
+                "}",
+                "",
+                "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]",
+                "#[serde(untagged)]",
+                "pub enum AllValue {",
+                "    Integer(i32),",
+                "    String(String),",
+                "}",
+                "",
+                "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]",
+                "pub enum AllType {",
+                "    #[serde(rename = \"admin\")]",
+                "    Admin,",
+                "    #[serde(rename = \"user\")]",
+                "    User,",
+                "}",
+                "",
+                "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]",
+                "pub struct AllOwner {",
+                "    #[serde(rename = \"user-name\")]",
+                "    pub user_name: String,",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.as_string(Strategy::Rust)
+        );
+    }
+
+    #[test]
+    fn keyword_variants() {
+        let value = ijson!([{ "scope": "self" }, { "scope": "crate" }, { "scope": "self" }]);
+        let typing = Typing::from_items("All", value);
+
+        self::assert_eq!(
+            [
+                "use serde::{Deserialize, Serialize};",
+                "",
+                "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]",
+                "pub struct All {",
+                "    pub scope: AllScope,",
+                "}",
+                "",
+                "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]",
+                "pub enum AllScope {",
+                "    #[serde(rename = \"self\")]",
+                "    Self_,",
+                "    #[serde(rename = \"crate\")]",
+                "    Crate,",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.as_string(Strategy::Rust)
+        );
+    }
+
+    #[test]
+    fn colliding_fields() {
+        let value = ijson!({ "userName": "a", "user_name": "b", "a-b": true, "a_b": false, "": 1 });
+        let typing = Typing::from_items("All", value);
+
+        self::assert_eq!(
+            [
+                "use serde::{Deserialize, Serialize};",
+                "",
+                "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]",
+                "pub struct All {",
+                "    #[serde(rename = \"userName\")]",
+                "    pub user_name: String,",
+                "    #[serde(rename = \"user_name\")]",
+                "    pub user_name_2: String,",
+                "    #[serde(rename = \"a-b\")]",
+                "    pub a_b: bool,",
+                "    #[serde(rename = \"a_b\")]",
+                "    pub a_b_2: bool,",
+                "    #[serde(rename = \"\")]",
+                "    pub field: i32,",
+                "}",
+                "",
+            ]
+            .join("\n"),
+            typing.as_string(Strategy::Rust)
+        );
+    }
+
+    #[test]
+    fn identifier() {
+        self::assert_eq!(super::identifier("type"), "type_");
+        self::assert_eq!(super::identifier("self"), "self_");
+        self::assert_eq!(super::identifier("Self"), "Self_");
+        self::assert_eq!(super::identifier("box"), "box_");
+        self::assert_eq!(super::identifier("1st"), "_1st");
+        self::assert_eq!(super::identifier("a-b"), "a_b");
+        self::assert_eq!(super::identifier(""), "_");
+    }
+}
@@ -0,0 +1,416 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use petgraph::{
+    graph::NodeIndex,
+    visit::{Reversed, Topo},
+};
+
+use super::{
+    json_schema::template_pattern, modules, referenced_objects, Declaration, Module, TypingStrategy,
+};
+use crate::{
+    regex, utils, BigIntStyle, Literal, NumberRange, Settings, StringFormat, Typing, TypingNode,
+};
+
+pub(super) struct Zod;
+impl TypingStrategy for Zod {
+    fn fmt_typing(typing: &Typing, settings: &Settings) -> String {
+        Zod::fmt_typings(std::slice::from_ref(typing), settings)
+    }
+
+    fn fmt_typings(typings: &[Typing], settings: &Settings) -> String {
+        format!(
+            "import {{ z }} from {1}zod{1};\n\n{0}\n",
+            Zod::all_declarations(typings, settings)
+                .into_iter()
+                .map(|declaration| declaration.content)
+                .join("\n\n"),
+            settings.string_delimiter
+        )
+    }
+
+    fn fmt_modules(typings: &[Typing], settings: &Settings) -> Option<Vec<Module>> {
+        let declarations = Zod::all_declarations(typings, settings);
+        let header = format!(
+            "import {{ z }} from {0}zod{0};\n",
+            settings.string_delimiter
+        );
+
+        Some(modules(declarations, &header, settings))
+    }
+}
+
+impl Zod {
+    /// Declarations of every typing, objects of different typings never sharing a name
+    fn all_declarations(typings: &[Typing], settings: &Settings) -> Vec<Declaration> {
+        let names = Zod::object_names(typings);
+
+        typings
+            .iter()
+            .zip(&names)
+            .flat_map(|(typing, names)| Zod::declarations(typing, names, settings))
+            .collect()
+    }
+
+    /// Schema and type declarations of every object of a typing, each one referencing the
+    /// schemas of the objects nested in it
+    fn declarations(
+        typing: &Typing,
+        names: &HashMap<NodeIndex, String>,
+        settings: &Settings,
+    ) -> Vec<Declaration> {
+        let graph = &typing.graph;
+
+        let graph_adaptor = Reversed(graph);
+        let mut topo = Topo::new(&graph_adaptor);
+
+        let mut node_value: HashMap<NodeIndex, String> = HashMap::new();
+        let mut declarations = vec![];
+        while let Some(nx) = topo.next(&graph_adaptor) {
+            let value = match &graph[nx] {
+                TypingNode::Null => String::from("z.null()"),
+                TypingNode::Boolean => String::from("z.boolean()"),
+                TypingNode::Number(range) => number(range, settings),
+                TypingNode::String => String::from("z.string()"),
+                TypingNode::Format(f) => string_format(f, settings),
+                TypingNode::Literal(Literal::Template(t)) => {
+                    format!(
+                        "z.string().regex(/{}/)",
+                        template_pattern(t).replace('/', "\\/")
+                    )
+                }
+                TypingNode::Literal(l) => {
+                    format!("z.literal({})", l.to_string(&settings.string_delimiter))
+                }
+                TypingNode::Array { .. } => {
+                    format!("z.array({})", union(typing, nx, &node_value, settings))
+                }
+                TypingNode::Record { .. } => {
+                    format!("z.record({})", union(typing, nx, &node_value, settings))
+                }
+                TypingNode::Tuple { .. } => {
+                    let elements: Vec<_> = typing.children(nx).collect();
+                    let required = elements
+                        .iter()
+                        .filter(|n_nx| !graph[**n_nx].as_tuple_element().unwrap().1)
+                        .count();
+
+                    // Every accepted length is its own tuple since zod tuples have a fixed length
+                    let tuples: Vec<_> = (required..=elements.len())
+                        .map(|len| {
+                            let values = elements[..len].iter().map(|n_nx| &node_value[n_nx]);
+                            format!("z.tuple([{}])", values.format(", "))
+                        })
+                        .collect();
+
+                    match tuples.len() {
+                        1 => tuples.into_iter().next().unwrap(),
+                        _ => format!("z.union([{}])", tuples.join(", ")),
+                    }
+                }
+                TypingNode::ObjectEntry { .. } | TypingNode::TupleElement { .. } => {
+                    union(typing, nx, &node_value, settings)
+                }
+                TypingNode::Object(_) if typing.is_union(nx) => {
+                    let name = &names[&nx];
+                    let variants = typing.children(nx).map(|n_nx| &node_value[&n_nx]);
+
+                    declarations.push((
+                        nx,
+                        format!(
+                            "export const {0}Schema = z.union([{1}]);\n\
+                             export type {0} = z.infer<typeof {0}Schema>;",
+                            name,
+                            variants.format(", ")
+                        ),
+                    ));
+
+                    format!("{}Schema", name)
+                }
+                TypingNode::Object(_) => {
+                    let name = &names[&nx];
+                    let entries = typing
+                        .children(nx)
+                        .map(|n_nx| {
+                            let (key, optional) = graph[n_nx].as_object_entry().unwrap();
+                            let key = utils::property_key(key, &settings.string_delimiter);
+                            let optional = if optional { ".optional()" } else { "" };
+
+                            format!(
+                                "{}{}: {}{},\n",
+                                settings.indentation, key, node_value[&n_nx], optional
+                            )
+                        })
+                        .join("");
+
+                    declarations.push((
+                        nx,
+                        format!(
+                            "export const {0}Schema = z.object({{\n{1}}});\n\
+                             export type {0} = z.infer<typeof {0}Schema>;",
+                            name, entries
+                        ),
+                    ));
+
+                    format!("{}Schema", name)
+                }
+            };
+
+            node_value.insert(nx, value);
+        }
+
+        declarations
+            .into_iter()
+            .map(|(nx, content)| {
+                let name = names[&nx].clone();
+                let references = referenced_objects(typing, nx)
+                    .into_iter()
+                    .map(|n_nx| format!("{}Schema", names[&n_nx]))
+                    .collect();
+
+                Declaration {
+                    exports: vec![format!("{}Schema", name), name.clone()],
+                    name,
+                    references,
+                    content,
+                }
+            })
+            .collect()
+    }
+
+    /// Names every object of every typing after its path in the tree strategy, `All.Glossary`
+    /// becomes `AllGlossary`
+    fn object_names(typings: &[Typing]) -> Vec<HashMap<NodeIndex, String>> {
+        let mut seen: HashMap<String, usize> = HashMap::new();
+
+        typings
+            .iter()
+            .map(|typing| {
+                typing
+                    .object_paths()
+                    .into_iter()
+                    .map(|(nx, path)| {
+                        let name = path.segments.concat();
+                        let count = seen.entry(name.clone()).or_insert(0);
+                        *count += 1;
+
+                        match count {
+                            1 => (nx, name),
+                            _ => (nx, format!("{}{}", name, count)),
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Schema validating a number, `.int()` for integers and the observed bounds with the bounds
+/// option. Integers out of the safe integer range follow the bigint style.
+fn number(range: &NumberRange, settings: &Settings) -> String {
+    if range.is_unsafe() {
+        match settings.bigint_style {
+            BigIntStyle::BigInt => return String::from("z.bigint()"),
+            BigIntStyle::String | BigIntStyle::Branded => return String::from("z.string()"),
+            BigIntStyle::Number => {}
+        }
+    }
+
+    let mut schema = String::from("z.number()");
+    if range.is_integer() {
+        schema += ".int()";
+    }
+    if settings.number_bounds {
+        schema += &format!(
+            ".min({}).max({})",
+            range.bound(range.min),
+            range.bound(range.max)
+        );
+    }
+
+    schema
+}
+
+/// Schema validating a formatted string, dates are coerced into `Date`s with the dates option
+/// and integers into bigints with the bigint style
+fn string_format(format: &StringFormat, settings: &Settings) -> String {
+    let delimiter = &settings.string_delimiter;
+
+    match format {
+        StringFormat::DateTime | StringFormat::Date if settings.dates => {
+            String::from("z.coerce.date()")
+        }
+        StringFormat::DateTime => String::from("z.string().datetime({ offset: true })"),
+        StringFormat::Date => String::from("z.string().date()"),
+        StringFormat::Int64 => String::from("z.string().regex(/^-?\\d+$/)"),
+        StringFormat::Uuid => String::from("z.string().uuid()"),
+        StringFormat::Email => String::from("z.string().email()"),
+        StringFormat::Uri => String::from("z.string().url()"),
+        StringFormat::Ipv4 => format!("z.string().ip({{ version: {0}v4{0} }})", delimiter),
+        StringFormat::Ipv6 => format!("z.string().ip({{ version: {0}v6{0} }})", delimiter),
+        StringFormat::Base64 => String::from("z.string().base64()"),
+        StringFormat::HexColor => format!(
+            "z.string().regex(/{}/)",
+            regex::HEX_COLOR.as_str().replace('/', "\\/")
+        ),
+    }
+}
+
+/// Schema accepting any of the children of `nx`, string literals are grouped in a `z.enum`
+fn union(
+    typing: &Typing,
+    nx: NodeIndex,
+    node_value: &HashMap<NodeIndex, String>,
+    settings: &Settings,
+) -> String {
+    let children: Vec<_> = typing.children(nx).collect();
+    let (literals, mut values): (Vec<_>, Vec<_>) = children
+        .iter()
+        .filter_map(|n_nx| node_value.get(n_nx).map(|v| (n_nx, v.clone())))
+        .partition(|(n_nx, _)| {
+            matches!(
+                typing.graph[**n_nx],
+                TypingNode::Literal(Literal::String(_))
+            )
+        });
+
+    match literals.len() {
+        0 => {}
+        1 => values.extend(literals),
+        _ => {
+            let literals = literals
+                .iter()
+                .map(|(n_nx, _)| match &typing.graph[**n_nx] {
+                    TypingNode::Literal(l) => l.to_string(&settings.string_delimiter),
+                    _ => unreachable!(),
+                })
+                .join(", ");
+
+            values.push((&nx, format!("z.enum([{}])", literals)));
+        }
+    }
+
+    match values.len() {
+        0 => String::from("z.unknown()"),
+        1 => values.into_iter().next().unwrap().1,
+        _ => format!(
+            "z.union([{}])",
+            values.into_iter().map(|(_, v)| v).join(", ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ijson::ijson;
+    use pretty_assertions::assert_eq;
+
+    use crate::{strategy::Strategy, Settings, Typing};
+
+    #[test]
+    fn zod() {
+        let value = ijson!([
+            {
+                "id": 1,
+                "type": "admin",
+                "point": [1, "a"],
+                "owner": { "user-name": "n" },
+                "value": 1,
+            },
+            {
+                "id": 2,
+                "type": "user",
+                "point": [2, "b", true],
+                "owner": null,
+                "value": "1",
+                "extra": true,
+            },
+            {
+                "id": 3,
+                "type": "user",
+                "point": [3, "c"],
+                "owner": null,
+                "value": 2,
+            },
+        ]);
+        let typing = Typing::from_items("All", value);
+
+        self::assert_eq!(
+            [
+                "import { z } from \"zod\";",
+                "",
+                "export const AllOwnerSchema = z.object({",
+                "    \"user-name\": z.string(),",
+                "});",
+                "export type AllOwner = z.infer<typeof AllOwnerSchema>;",
+                "",
+                "export const AllSchema = z.object({",
+                "    id: z.number().int(),",
+                "    type: z.enum([\"admin\", \"user\"]),",
+                "    point: z.union([z.tuple([z.number().int(), z.string()]), \
+                 z.tuple([z.number().int(), z.string(), z.boolean()])]),",
+                "    owner: z.union([z.null(), AllOwnerSchema]),",
+                "    value: z.union([z.number().int(), z.string()]),",
+                "    extra: z.boolean().optional(),",
+                "});",
+                "export type All = z.infer<typeof AllSchema>;",
+                "",
+            ]
+            .join("\n"),
+            typing.as_string(Strategy::Zod)
+        );
+    }
+
+    #[test]
+    fn string_formats() {
+        let value = ijson!([
+            { "email": "a@example.com", "at": "2021-10-03T10:00:00Z" },
+            { "email": "b@example.com", "at": "2021-10-04T10:00:00Z" },
+        ]);
+        let settings = Settings {
+            strategy: Strategy::Zod,
+            formats: true,
+            dates: true,
+            ..Settings::default()
+        };
+        let typing = Typing::from_items_with_settings("All", value, &settings);
+
+        self::assert_eq!(
+            [
+                "import { z } from \"zod\";",
+                "",
+                "export const AllSchema = z.object({",
+                "    email: z.string().email(),",
+                "    at: z.coerce.date(),",
+                "});",
+                "export type All = z.infer<typeof AllSchema>;",
+                "",
+            ]
+            .join("\n"),
+            typing.render(&settings)
+        );
+    }
+
+    #[test]
+    fn quoted_keys() {
+        let value = ijson!({ "a-b": 1, "say \"hi\"": 2, "back\\slash": 3 });
+        let typing = Typing::from_items("All", value);
+
+        self::assert_eq!(
+            [
+                "import { z } from \"zod\";",
+                "",
+                "export const AllSchema = z.object({",
+                "    \"a-b\": z.number().int(),",
+                "    \"say \\\"hi\\\"\": z.number().int(),",
+                "    \"back\\\\slash\": z.number().int(),",
+                "});",
+                "export type All = z.infer<typeof AllSchema>;",
+                "",
+            ]
+            .join("\n"),
+            typing.as_string(Strategy::Zod)
+        );
+    }
+}
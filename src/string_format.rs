@@ -0,0 +1,167 @@
+use fancy_regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{number_range::MAX_SAFE_INTEGER, regex};
+
+/// Well known format of a string, detected when every value of a key shares it
+#[derive(Serialize, Deserialize, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum StringFormat {
+    DateTime,
+    Date,
+    /// Integer out of the range javascript numbers represent exactly, such as 64 bits ids
+    Int64,
+    Uuid,
+    Email,
+    Uri,
+    Ipv4,
+    Ipv6,
+    HexColor,
+    Base64,
+}
+
+/// How string formats are rendered in typescript outputs
+#[derive(Serialize, Deserialize, Debug, Hash, PartialEq, Eq, Clone)]
+pub enum FormatStyle {
+    /// `string`
+    Plain,
+    /// `string & { readonly __format: "uuid" }`
+    Branded,
+    /// `string` with a `/** @format uuid */` comment on the key
+    JsDoc,
+}
+
+impl StringFormat {
+    /// Formats in the order they are tried, from the most to the least specific
+    const ALL: [StringFormat; 10] = [
+        Self::DateTime,
+        Self::Date,
+        Self::Int64,
+        Self::Uuid,
+        Self::Email,
+        Self::Uri,
+        Self::Ipv4,
+        Self::Ipv6,
+        Self::HexColor,
+        Self::Base64,
+    ];
+
+    /// Format of a single string
+    pub fn of(s: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|format| format.matches(s))
+    }
+
+    /// Format shared by every string, `None` when there are no strings or they differ
+    pub fn detect<'a, T: IntoIterator<Item = &'a str>>(strs: T) -> Option<Self> {
+        let mut strs = strs.into_iter();
+        let format = Self::of(strs.next()?)?;
+
+        strs.all(|s| format.matches(s)).then_some(format)
+    }
+
+    /// Whether `s` has this format, integers must also be out of the safe integer range
+    pub fn matches(&self, s: &str) -> bool {
+        let matched = self.regex().is_match(s).unwrap_or(false);
+
+        match self {
+            Self::Int64 => {
+                let range = i64::MIN as i128..=u64::MAX as i128;
+                matched
+                    && s.parse::<i128>().is_ok_and(|n| {
+                        range.contains(&n) && n.unsigned_abs() > MAX_SAFE_INTEGER as u128
+                    })
+            }
+            _ => matched,
+        }
+    }
+
+    /// Name of the format as used by JSON Schema's `format` keyword
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::DateTime => "date-time",
+            Self::Date => "date",
+            Self::Int64 => "int64",
+            Self::Uuid => "uuid",
+            Self::Email => "email",
+            Self::Uri => "uri",
+            Self::Ipv4 => "ipv4",
+            Self::Ipv6 => "ipv6",
+            Self::HexColor => "hex-color",
+            Self::Base64 => "base64",
+        }
+    }
+
+    pub fn regex(&self) -> &'static Regex {
+        match self {
+            Self::DateTime => &regex::DATE_TIME,
+            Self::Date => &regex::DATE,
+            Self::Int64 => &regex::INTEGER,
+            Self::Uuid => &regex::UUID,
+            Self::Email => &regex::EMAIL,
+            Self::Uri => &regex::URI,
+            Self::Ipv4 => &regex::IPV4,
+            Self::Ipv6 => &regex::IPV6,
+            Self::HexColor => &regex::HEX_COLOR,
+            Self::Base64 => &regex::BASE64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn of() {
+        let cases = [
+            ("2021-10-03T10:00:00Z", Some(StringFormat::DateTime)),
+            (
+                "2021-10-03T10:00:00.123+02:00",
+                Some(StringFormat::DateTime),
+            ),
+            ("2021-10-03", Some(StringFormat::Date)),
+            ("1445185496281014272", Some(StringFormat::Int64)),
+            ("-9007199254740993", Some(StringFormat::Int64)),
+            ("9007199254740991", None),
+            ("99999999999999999999", None),
+            (
+                "123e4567-e89b-12d3-a456-426614174000",
+                Some(StringFormat::Uuid),
+            ),
+            ("jane.doe@example.com", Some(StringFormat::Email)),
+            ("https://example.com/a?b=c", Some(StringFormat::Uri)),
+            ("192.168.0.1", Some(StringFormat::Ipv4)),
+            ("2001:db8::1", Some(StringFormat::Ipv6)),
+            ("::1", Some(StringFormat::Ipv6)),
+            (
+                "2001:0db8:85a3:0000:0000:8a2e:0370:7334",
+                Some(StringFormat::Ipv6),
+            ),
+            ("00:1a:2b:3c:4d:5e", None),
+            ("ab:cd:ef", None),
+            ("2001:db8::1::2", None),
+            ("1:2:3:4:5:6:7:8:9", None),
+            ("#ff00aa", Some(StringFormat::HexColor)),
+            ("SGVsbG8gV29ybGQhIQ==", Some(StringFormat::Base64)),
+            ("2021-10-03T10:00:00", None),
+            ("10:00:00", None),
+            ("256.1.1.1", None),
+            ("5d41402abc4b2a76b9719d911017c592", None),
+            ("abcdefghijklmnop", None),
+            ("example glossary", None),
+        ];
+
+        for (s, format) in cases {
+            assert_eq!(format, StringFormat::of(s), "{}", s);
+        }
+    }
+
+    #[test]
+    fn detect() {
+        assert_eq!(
+            Some(StringFormat::Date),
+            StringFormat::detect(vec!["2021-10-03", "2021-10-04"])
+        );
+        assert_eq!(None, StringFormat::detect(vec!["2021-10-03", "today"]));
+        assert_eq!(None, StringFormat::detect(vec![]));
+    }
+}
@@ -1,27 +1,37 @@
-use std::hash::Hash;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    fmt::{self, Display},
+    hash::Hash,
+};
 
 use convert_case::{Case, Casing};
-use ijson::{Destructured, IValue, ValueType};
+use ijson::{Destructured, IArray, IValue, ValueType};
 use itertools::Itertools;
 use petgraph::{graph::NodeIndex, Graph};
 
-use crate::{items::Items, regex, strategy::Strategy, Literal};
+use crate::{
+    items::Items, regex, strategy::Strategy, Literal, NumberRange, Settings, StringFormat, TypePath,
+};
 
 pub type TypingGraph = Graph<TypingNode, ()>;
 
+/// Longest array that can be inferred as a tuple
+const MAX_TUPLE_LEN: usize = 8;
+
 /// Represents a typescript interface with a namespace associated with it
 /// ## Examples
 /// ```
-/// use serde_json::json;
-/// use json_typings::{Typing, Items};
+/// use ijson::ijson;
+/// use json_typings::Typing;
 ///
 /// let values = vec![
-///     json!({
+///     ijson!({
 ///         "a": true,
 ///         "b": 1,
 ///         "c": [1,2,3,4,5],
 ///     }),
-///     json!({
+///     ijson!({
 ///         "a": true,
 ///         "c": [1,2,3,4,5],
 ///     }),
@@ -50,13 +60,27 @@ pub struct Typing {
 pub enum TypingNode {
     Null,
     Boolean,
-    Number,
+    Number(NumberRange),
     String,
     Array {
         object_node: NodeIndex,
         key: String,
     },
+    Record {
+        object_node: NodeIndex,
+        key: String,
+    },
+    Tuple {
+        object_node: NodeIndex,
+        key: String,
+    },
+    TupleElement {
+        index: usize,
+        optional: bool,
+        object_node: NodeIndex,
+    },
     Literal(Literal),
+    Format(StringFormat),
     Object(String),
     ObjectEntry {
         key: String,
@@ -65,9 +89,6 @@ pub enum TypingNode {
     },
 }
 
-#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-pub struct ObjectNode(pub usize);
-
 impl Typing {
     pub fn new(name: &str) -> Self {
         Self {
@@ -82,17 +103,51 @@ impl Typing {
     ///
     /// * `name` - Name of the interface and namespace for this typing
     /// * `values` - A vector of Map<String, Value> used to generate the typing
-    pub fn from_items<'a, T: Into<Items>>(name: &str, items: T) -> Self {
+    pub fn from_items<T: Into<Items>>(name: &str, items: T) -> Self {
+        Self::from_items_with_settings(name, items, &Settings::default())
+    }
+
+    /// Generates typing based on a vector of objects, following the inference options of
+    /// `settings`
+    pub fn from_items_with_settings<T: Into<Items>>(
+        name: &str,
+        items: T,
+        settings: &Settings,
+    ) -> Self {
+        let threshold = settings.record_threshold;
         let mut typing = Self::new(name);
         let main_node = typing
             .graph
             .add_node(TypingNode::Object(String::from(name)));
 
-        let items = items.into();
+        let items: Items = items.into();
+        let discriminator = match settings.discriminated_unions {
+            true => items.discriminator(settings.max_literal_values(), settings.literal_max_length),
+            false => None,
+        };
+
+        // Objects along with the items they are inferred from and their discriminator key,
+        // discriminated root items make the root a union of their variants
+        let mut node_items: Vec<(NodeIndex, Items, Option<String>)> = match discriminator {
+            Some(key) => {
+                let variants = items.variants(&key);
+                let names = variant_names("", variants.iter().map(|(value, _)| value.as_str()));
 
-        let mut node_items: Vec<(NodeIndex, Items)> = vec![(main_node, items)];
+                variants
+                    .into_iter()
+                    .zip(names)
+                    .map(|((_, items), object_name)| {
+                        let object_node = typing.graph.add_node(TypingNode::Object(object_name));
+                        typing.graph.add_edge(main_node, object_node, ());
 
-        while let Some((node, items)) = node_items.pop() {
+                        (object_node, items, Some(key.clone()))
+                    })
+                    .collect()
+            }
+            None => vec![(main_node, items, None)],
+        };
+
+        while let Some((node, items, discriminator)) = node_items.pop() {
             for item_entry in items.entries() {
                 let entry_node = typing.graph.add_node(TypingNode::ObjectEntry {
                     key: item_entry.key.clone(),
@@ -101,15 +156,25 @@ impl Typing {
                 });
                 typing.graph.add_edge(node, entry_node, ());
 
-                let mut parent_values: Vec<(NodeIndex, Vec<IValue>)> = item_entry
-                    .values
-                    .into_iter()
-                    .group_by(|v| std::mem::discriminant(v))
-                    .into_iter()
-                    .map(|(_, group)| (entry_node, group.into_iter().collect()))
-                    .collect();
+                // Every object of a variant has the same discriminator, always a literal
+                if discriminator.as_ref() == Some(&item_entry.key) {
+                    let value = item_entry.values[0].as_string().unwrap();
+                    let literal_node = typing
+                        .graph
+                        .add_node(TypingNode::Literal(Literal::from_str(value)));
+                    typing.graph.add_edge(entry_node, literal_node, ());
 
-                while let Some((parent, values)) = parent_values.pop() {
+                    continue;
+                }
+
+                let key = item_entry.key.clone();
+                let mut parent_values: Vec<(NodeIndex, String, Vec<IValue>)> =
+                    group_by_type(item_entry.values)
+                        .into_iter()
+                        .map(|group| (entry_node, key.clone(), group))
+                        .collect();
+
+                while let Some((parent, name, values)) = parent_values.pop() {
                     let values = values;
 
                     match values[0].type_() {
@@ -122,28 +187,75 @@ impl Typing {
                             typing.graph.add_edge(parent, bool_node, ());
                         }
                         ValueType::Number => {
-                            let number_node = typing.graph.add_node(TypingNode::Number);
-                            typing.graph.add_edge(parent, number_node, ());
+                            let uniques: Vec<_> = values.iter().unique().collect();
+                            let duplicates = values.len() - uniques.len();
+                            let repeated = duplicates > 0
+                                && duplicates * 100
+                                    >= settings.number_literal_min_repetition * values.len();
+                            // Repeated floats are measurements rather than sets of codes
+                            let integers = values
+                                .iter()
+                                .filter_map(|v| v.as_number())
+                                .all(|n| !n.has_decimal_point());
+
+                            if repeated
+                                && integers
+                                && uniques.len() <= settings.number_literal_max_values
+                            {
+                                uniques
+                                    .into_iter()
+                                    .filter_map(|v| v.as_number())
+                                    .map(|n| TypingNode::Literal(Literal::from_number(n)))
+                                    .for_each(|t| {
+                                        let typing_node = typing.graph.add_node(t);
+
+                                        typing.graph.add_edge(parent, typing_node, ());
+                                    });
+                            } else {
+                                let range = NumberRange::from_numbers(
+                                    values.iter().filter_map(|v| v.as_number()),
+                                )
+                                .unwrap();
+                                let number_node = typing.graph.add_node(TypingNode::Number(range));
+                                typing.graph.add_edge(parent, number_node, ());
+                            }
                         }
                         ValueType::String => {
                             let strs: Vec<_> = values
                                 .iter()
                                 .filter_map(|v| v.as_string())
-                                .filter(|s| s.len() > 0)
+                                .filter(|s| !s.is_empty())
                                 .collect();
 
                             let uniques: Vec<_> = strs.iter().unique().collect();
                             let max_len = uniques.iter().map(|v| v.len()).max().unwrap_or(0);
 
                             let duplicates = strs.len() - uniques.len();
+                            let repeated = duplicates > 0
+                                && duplicates * 100 >= settings.literal_min_repetition * strs.len();
                             let numbers = uniques
                                 .iter()
                                 .filter_map(|v| regex::NUMBER.is_match(v).ok())
                                 .filter(|v| *v)
                                 .count();
+                            let numeric =
+                                numbers * 100 > settings.template_min_numeric * uniques.len();
+                            let format = match settings.formats {
+                                true => StringFormat::detect(strs.iter().map(|s| s.as_str())),
+                                false => None,
+                            };
 
-                            match (max_len, duplicates) {
-                                (1..=32, 1..) if numbers > uniques.len() / 2 => {
+                            match (format, max_len, repeated) {
+                                (Some(format), _, _) => {
+                                    let format_node =
+                                        typing.graph.add_node(TypingNode::Format(format));
+                                    typing.graph.add_edge(parent, format_node, ());
+                                }
+                                (None, 1.., true)
+                                    if settings.templates
+                                        && max_len <= settings.template_max_length
+                                        && numeric =>
+                                {
                                     uniques
                                         .iter()
                                         .map(|&&text| {
@@ -170,14 +282,19 @@ impl Typing {
                                             typing.graph.add_edge(parent, node, ());
                                         });
                                 }
-                                (1..=16, 1..) => uniques
-                                    .into_iter()
-                                    .map(|s| TypingNode::Literal(Literal::from_str(s)))
-                                    .for_each(|t| {
-                                        let typing_node = typing.graph.add_node(t);
+                                (None, 1.., true)
+                                    if max_len <= settings.literal_max_length
+                                        && uniques.len() <= settings.max_literal_values() =>
+                                {
+                                    uniques
+                                        .into_iter()
+                                        .map(|s| TypingNode::Literal(Literal::from_str(s)))
+                                        .for_each(|t| {
+                                            let typing_node = typing.graph.add_node(t);
 
-                                        typing.graph.add_edge(parent, typing_node, ());
-                                    }),
+                                            typing.graph.add_edge(parent, typing_node, ());
+                                        })
+                                }
                                 _ => {
                                     let string_node = typing.graph.add_node(TypingNode::String);
                                     typing.graph.add_edge(parent, string_node, ());
@@ -185,32 +302,95 @@ impl Typing {
                             };
                         }
                         ValueType::Array => {
-                            let array_node = typing.graph.add_node(TypingNode::Array {
-                                object_node: node,
-                                key: item_entry.key.clone(),
-                            });
-                            typing.graph.add_edge(parent, array_node, ());
-
-                            values
+                            let arrays: Vec<_> = values
                                 .into_iter()
                                 .filter_map(|v| match v.destructure() {
                                     Destructured::Array(a) => Some(a),
                                     _ => None,
                                 })
-                                .flatten()
-                                .group_by(|v| std::mem::discriminant(&v.type_()))
-                                .into_iter()
-                                .for_each(|(_, group)| {
-                                    parent_values.push((array_node, group.into_iter().collect()))
+                                .collect();
+
+                            if let Some(positions) = tuple_positions(&arrays) {
+                                let tuple_node = typing.graph.add_node(TypingNode::Tuple {
+                                    object_node: node,
+                                    key: item_entry.key.clone(),
                                 });
+                                typing.graph.add_edge(parent, tuple_node, ());
+
+                                let min_len = arrays.iter().map(|a| a.len()).min().unwrap_or(0);
+                                for (index, values) in positions.into_iter().enumerate() {
+                                    let element_node =
+                                        typing.graph.add_node(TypingNode::TupleElement {
+                                            index,
+                                            optional: index >= min_len,
+                                            object_node: node,
+                                        });
+                                    typing.graph.add_edge(tuple_node, element_node, ());
+
+                                    let name = format!("{}_element_{}", name, index);
+                                    parent_values.push((element_node, name, values));
+                                }
+
+                                continue;
+                            }
+
+                            let array_node = typing.graph.add_node(TypingNode::Array {
+                                object_node: node,
+                                key: item_entry.key.clone(),
+                            });
+                            typing.graph.add_edge(parent, array_node, ());
+
+                            for group in group_by_type(arrays.into_iter().flatten()) {
+                                parent_values.push((array_node, name.clone(), group));
+                            }
+                        }
+                        ValueType::Object if Items::from(values.clone()).is_record(threshold) => {
+                            let record_node = typing.graph.add_node(TypingNode::Record {
+                                object_node: node,
+                                key: item_entry.key.clone(),
+                            });
+                            typing.graph.add_edge(parent, record_node, ());
+
+                            for group in group_by_type(Items::from(values).values()) {
+                                parent_values.push((record_node, name.clone(), group));
+                            }
                         }
                         ValueType::Object => {
-                            let object_name = item_entry.key.to_case(Case::Pascal);
-                            let object_node =
-                                typing.graph.add_node(TypingNode::Object(object_name));
-                            typing.graph.add_edge(parent, object_node, ());
+                            let items = Items::from(values);
+                            let discriminator = match settings.discriminated_unions {
+                                true => items.discriminator(
+                                    settings.max_literal_values(),
+                                    settings.literal_max_length,
+                                ),
+                                false => None,
+                            };
+
+                            // One object per variant of a discriminated union, named after
+                            // the value of its discriminator
+                            let variants = match &discriminator {
+                                Some(key) => {
+                                    let variants = items.variants(key);
+                                    let names = variant_names(
+                                        &name,
+                                        variants.iter().map(|(value, _)| value.as_str()),
+                                    );
+
+                                    names
+                                        .into_iter()
+                                        .zip(variants)
+                                        .map(|(object_name, (_, items))| (object_name, items))
+                                        .collect()
+                                }
+                                None => vec![(name.to_case(Case::Pascal), items)],
+                            };
 
-                            node_items.push((object_node, values.into()));
+                            for (object_name, items) in variants {
+                                let object_node =
+                                    typing.graph.add_node(TypingNode::Object(object_name));
+                                typing.graph.add_edge(parent, object_node, ());
+
+                                node_items.push((object_node, items, discriminator.clone()));
+                            }
                         }
                     }
                 }
@@ -220,38 +400,284 @@ impl Typing {
         typing
     }
 
+    /// Merges structurally equal objects into a single shared object node
+    ///
+    /// Two objects are equal when they have the same keys, with the same optionality and the
+    /// same types, regardless of their names and key order. Every reference to a duplicate is
+    /// redirected to the first equal object found in depth first order and the duplicates are
+    /// dropped from the graph.
+    pub fn deduplicate(&mut self) {
+        let mut signatures = HashMap::new();
+        let mut canonicals: HashMap<String, NodeIndex> = HashMap::new();
+        let mut canonical = HashMap::new();
+        for (nx, _) in self.object_paths() {
+            let signature = self.signature(nx, &mut signatures);
+            let canonical_nx = *canonicals.entry(signature).or_insert(nx);
+
+            if canonical_nx != nx {
+                canonical.insert(nx, canonical_nx);
+            }
+        }
+
+        if canonical.is_empty() {
+            return;
+        }
+
+        for (&nx, &canonical_nx) in &canonical {
+            self.union_ranges(nx, canonical_nx, &mut signatures);
+        }
+
+        self.rebuild(|nx| *canonical.get(&nx).unwrap_or(&nx));
+    }
+
+    /// Widens the number bounds under `into` with the bounds of the matching numbers under
+    /// `nx`, both having the same signature. Nested objects are left to their own canonical.
+    fn union_ranges(
+        &mut self,
+        nx: NodeIndex,
+        into: NodeIndex,
+        signatures: &mut HashMap<NodeIndex, String>,
+    ) {
+        if let (TypingNode::Number(range), TypingNode::Number(into_range)) =
+            (&self.graph[nx], &self.graph[into])
+        {
+            self.graph[into] = TypingNode::Number(into_range.union(range));
+            return;
+        }
+
+        let children = |nx| {
+            self.children(nx)
+                .filter(|&n_nx| !matches!(self.graph[n_nx], TypingNode::Object(_)))
+                .collect::<Vec<_>>()
+        };
+        let (children, into_children) = (children(nx), children(into));
+
+        for n_nx in children {
+            let signature = self.signature(n_nx, signatures);
+            let matching = into_children
+                .iter()
+                .find(|&&into_nx| self.signature(into_nx, signatures) == signature);
+
+            if let Some(&into_nx) = matching {
+                self.union_ranges(n_nx, into_nx, signatures);
+            }
+        }
+    }
+
+    /// Rebuilds the graph with the nodes reachable from the root, pointing every edge to
+    /// `resolve(target)`
+    pub(crate) fn rebuild(&mut self, resolve: impl Fn(NodeIndex) -> NodeIndex) {
+        let root = NodeIndex::new(0);
+        let mut reachable = HashSet::new();
+        let mut stack = vec![root];
+        while let Some(nx) = stack.pop() {
+            if reachable.insert(nx) {
+                stack.extend(self.graph.neighbors(nx).map(&resolve));
+            }
+        }
+
+        let mut new_index = HashMap::new();
+        let mut graph = TypingGraph::new();
+        let kept: Vec<_> = self
+            .graph
+            .node_indices()
+            .filter(|nx| reachable.contains(nx))
+            .collect();
+        for (i, nx) in kept.iter().enumerate() {
+            new_index.insert(*nx, NodeIndex::new(i));
+        }
+        for nx in kept {
+            let node = match self.graph[nx].clone() {
+                TypingNode::Array { object_node, key } => TypingNode::Array {
+                    object_node: new_index[&object_node],
+                    key,
+                },
+                TypingNode::Record { object_node, key } => TypingNode::Record {
+                    object_node: new_index[&object_node],
+                    key,
+                },
+                TypingNode::Tuple { object_node, key } => TypingNode::Tuple {
+                    object_node: new_index[&object_node],
+                    key,
+                },
+                TypingNode::TupleElement {
+                    index,
+                    optional,
+                    object_node,
+                } => TypingNode::TupleElement {
+                    index,
+                    optional,
+                    object_node: new_index[&object_node],
+                },
+                TypingNode::ObjectEntry {
+                    key,
+                    optional,
+                    object_node,
+                } => TypingNode::ObjectEntry {
+                    key,
+                    optional,
+                    object_node: new_index[&object_node],
+                },
+                node => node,
+            };
+
+            graph.add_node(node);
+        }
+        for (nx, new_nx) in new_index.iter().sorted() {
+            for n_nx in self.children(*nx) {
+                graph.add_edge(*new_nx, new_index[&resolve(n_nx)], ());
+            }
+        }
+
+        self.graph = graph;
+    }
+
+    /// Structural signature of a node, ignoring object names and key order
+    fn signature(&self, nx: NodeIndex, signatures: &mut HashMap<NodeIndex, String>) -> String {
+        if let Some(signature) = signatures.get(&nx) {
+            return signature.clone();
+        }
+
+        let children: Vec<_> = self
+            .children(nx)
+            .map(|n_nx| self.signature(n_nx, signatures))
+            .sorted()
+            .dedup()
+            .collect();
+
+        let signature = match &self.graph[nx] {
+            TypingNode::Array { .. } => format!("Array<{}>", children.join(" | ")),
+            TypingNode::Record { .. } => format!("Record<{}>", children.join(" | ")),
+            TypingNode::Tuple { .. } => format!("[{}]", children.join(", ")),
+            TypingNode::TupleElement {
+                index, optional, ..
+            } => {
+                let optional = if *optional { "?" } else { "" };
+                format!("{}{}: {}", index, optional, children.join(" | "))
+            }
+            TypingNode::Object(_) => format!("{{{}}}", children.join("; ")),
+            // Objects whose numbers only differ by their bounds have the same shape
+            TypingNode::Number(range) => format!("Number({:?})", range.kind),
+            TypingNode::ObjectEntry { key, optional, .. } => {
+                let optional = if *optional { "?" } else { "" };
+                format!("{:?}{}: {}", key, optional, children.join(" | "))
+            }
+            node => format!("{:?}", node),
+        };
+
+        signatures.insert(nx, signature.clone());
+        signature
+    }
+
+    /// Whether the object `nx` is a union of the variants of a discriminated union instead
+    /// of an interface, only the root can be
+    pub fn is_union(&self, nx: NodeIndex) -> bool {
+        self.graph[nx].is_object() && self.children(nx).any(|n_nx| self.graph[n_nx].is_object())
+    }
+
+    /// Ancestry of every object node in depth first order, starting from the root object
+    ///
+    /// Objects shared by multiple parents only appear once, under the first parent found.
+    pub fn object_paths(&self) -> Vec<(NodeIndex, TypePath)> {
+        let root = NodeIndex::new(0);
+        let mut paths = vec![];
+        let mut visited = HashSet::new();
+
+        let mut stack = vec![(root, TypePath::new())];
+        while let Some((nx, path)) = stack.pop() {
+            let path = match &self.graph[nx] {
+                TypingNode::Object(name) => {
+                    if !visited.insert(nx) {
+                        continue;
+                    }
+
+                    let path = path + &TypePath::from([name.as_str()]);
+                    paths.push((nx, path.clone()));
+
+                    path
+                }
+                _ => path,
+            };
+
+            let children: Vec<_> = self.children(nx).collect();
+            stack.extend(children.into_iter().rev().map(|c| (c, path.clone())));
+        }
+
+        paths
+    }
+
+    /// Children of `node` in the order they were inserted in the graph
+    pub fn children(&self, node: NodeIndex) -> impl Iterator<Item = NodeIndex> {
+        let children: Vec<_> = self.graph.neighbors(node).collect();
+
+        children.into_iter().rev()
+    }
+
+    /// Formats this typing with `strategy` and the default settings
     pub fn as_string(&self, strategy: Strategy) -> String {
-        strategy.to_string(self)
+        let settings = Settings {
+            strategy,
+            ..Settings::default()
+        };
+
+        self.render(&settings)
+    }
+
+    /// Formats this typing with the strategy and style of `settings`
+    pub fn render(&self, settings: &Settings) -> String {
+        settings.strategy.clone().to_string(self, settings)
+    }
+}
+
+/// Names of the objects of the variants of a discriminated union, `prefix` followed by the
+/// value of their discriminator in PascalCase. Values only differing by their case are
+/// numbered.
+pub(crate) fn variant_names<'a, T: IntoIterator<Item = &'a str>>(
+    prefix: &str,
+    values: T,
+) -> Vec<String> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    values
+        .into_iter()
+        .map(|value| {
+            let name = format!("{}_{}", prefix, value).to_case(Case::Pascal);
+            let count = seen.entry(name.clone()).or_insert(0);
+            *count += 1;
+
+            match count {
+                1 => name,
+                _ => format!("{}{}", name, count),
+            }
+        })
+        .collect()
+}
+
+impl Display for Typing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(&Settings::default()).trim_end().fmt(f)
     }
 }
 
 impl TypingNode {
     pub fn is_object_entry(&self) -> bool {
-        match self {
-            TypingNode::ObjectEntry {
-                key: _,
-                optional: _,
-                object_node: _,
-            } => true,
-            _ => false,
-        }
+        matches!(self, TypingNode::ObjectEntry { .. })
     }
 
     pub fn is_array(&self) -> bool {
-        match self {
-            TypingNode::Array {
-                object_node: _,
-                key: _,
-            } => true,
-            _ => false,
-        }
+        matches!(self, TypingNode::Array { .. })
+    }
+
+    pub fn is_record(&self) -> bool {
+        matches!(self, TypingNode::Record { .. })
+    }
+
+    pub fn is_tuple(&self) -> bool {
+        matches!(self, TypingNode::Tuple { .. })
     }
 
     pub fn is_object(&self) -> bool {
-        match self {
-            TypingNode::Object(_) => true,
-            _ => false,
-        }
+        matches!(self, TypingNode::Object(_))
     }
 
     pub fn as_object(&self) -> Option<&str> {
@@ -271,22 +697,82 @@ impl TypingNode {
             _ => None,
         }
     }
+
+    pub fn as_tuple_element(&self) -> Option<(usize, bool)> {
+        match self {
+            Self::TupleElement {
+                index, optional, ..
+            } => Some((*index, *optional)),
+            _ => None,
+        }
+    }
+}
+
+/// Groups values by their json type, from the last type to the first so that popping the
+/// groups from a stack follows the order of `ValueType`
+fn group_by_type<T: IntoIterator<Item = IValue>>(values: T) -> Vec<Vec<IValue>> {
+    values
+        .into_iter()
+        .into_group_map_by(|v| v.type_())
+        .into_iter()
+        .sorted_by_key(|(value_type, _)| Reverse(*value_type))
+        .map(|(_, group)| group)
+        .collect()
+}
+
+/// Values at every position of `arrays` when they look like tuples: short arrays where each
+/// position always holds the same type, but not every position holds the same one
+///
+/// A single array is never a tuple, a mixed list can't be told apart from a fixed shape
+/// without a second sample.
+fn tuple_positions(arrays: &[IArray]) -> Option<Vec<Vec<IValue>>> {
+    let max_len = arrays.iter().map(|a| a.len()).max()?;
+    if arrays.len() < 2 || max_len > MAX_TUPLE_LEN || arrays.iter().any(|a| a.is_empty()) {
+        return None;
+    }
+
+    let positions: Vec<Vec<IValue>> = (0..max_len)
+        .map(|i| arrays.iter().filter_map(|a| a.get(i)).cloned().collect())
+        .collect();
+
+    let consistent = positions
+        .iter()
+        .all(|values| values.iter().map(|v| v.type_()).all_equal());
+    let heterogeneous = !positions.iter().map(|values| values[0].type_()).all_equal();
+
+    if consistent && heterogeneous {
+        Some(positions)
+    } else {
+        None
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::path::Path;
 
-    use ijson::IArray;
+    use ijson::{ijson, IArray};
     use petgraph::{dot::Dot, visit::Bfs};
+    use pretty_assertions::assert_eq;
 
     use crate::read_json;
 
     use super::*;
 
+    #[test]
+    fn interleaved_types() {
+        let value = ijson!([{ "v": "a" }, { "v": 1 }, { "v": "b" }, { "v": null }, { "v": 2 }]);
+        let typing = Typing::from_items("All", value);
+
+        self::assert_eq!(
+            typing.as_string(Strategy::Family),
+            "export interface All {\n    v: number | string | null;\n}\n"
+        );
+    }
+
     #[test]
     fn test() {
-        let value = read_json::file(&Path::new("./data/sample_a.json")).unwrap();
+        let value = read_json::file(Path::new("./data/sample_a.json")).unwrap();
 
         let values = match value.into_array() {
             Ok(a) => a,
@@ -308,4 +794,274 @@ mod tests {
         let graph = format!("{:?}", Dot::new(&typing.graph));
         std::fs::write("./data.graph", graph).unwrap();
     }
+
+    #[test]
+    fn deduplicate() {
+        let value = ijson!({
+            "billing": { "street": "a", "zip": 1 },
+            "shipping": { "zip": 2, "street": "b" },
+            "other": { "street": "c" },
+            "history": [{ "street": "d", "zip": 3 }],
+        });
+        let mut typing = Typing::from_items("All", value);
+        typing.deduplicate();
+
+        let objects: Vec<_> = typing
+            .graph
+            .node_weights()
+            .filter_map(TypingNode::as_object)
+            .collect();
+        self::assert_eq!(objects, vec!["All", "Billing", "Other"]);
+
+        let billing = typing.object_paths()[1].0;
+        let parents = typing
+            .graph
+            .neighbors_directed(billing, petgraph::Direction::Incoming)
+            .count();
+        self::assert_eq!(parents, 3);
+    }
+
+    #[test]
+    fn deduplicate_bounds() {
+        let value = ijson!({
+            "billing": { "street": "a", "zip": 1 },
+            "shipping": { "street": "b", "zip": 99 },
+        });
+        let mut typing = Typing::from_items("All", value);
+        typing.deduplicate();
+
+        let ranges: Vec<_> = typing
+            .graph
+            .node_weights()
+            .filter_map(|node| match node {
+                TypingNode::Number(range) => Some((range.min.0, range.max.0)),
+                _ => None,
+            })
+            .collect();
+        self::assert_eq!(ranges, vec![(1.0, 99.0)]);
+    }
+
+    #[test]
+    fn literal_thresholds() {
+        let value = ijson!([
+            { "kind": "a", "size": "10px", "code": "abc" },
+            { "kind": "a", "size": "10px", "code": "abc" },
+            { "kind": "b", "size": "12px", "code": "def" },
+            { "kind": "c", "size": "12px", "code": "ghi" },
+        ]);
+        let typing = |settings: Settings| {
+            let settings = Settings {
+                strategy: Strategy::Family,
+                ..settings
+            };
+
+            Typing::from_items_with_settings("All", value.clone(), &settings).render(&settings)
+        };
+
+        self::assert_eq!(
+            typing(Settings::default()),
+            [
+                "export interface All {",
+                "    kind: \"a\" | \"b\" | \"c\";",
+                "    size: `${number}px`;",
+                "    code: \"abc\" | \"def\" | \"ghi\";",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+        self::assert_eq!(
+            typing(Settings {
+                literal_max_values: 2,
+                literal_min_repetition: 50,
+                templates: false,
+                ..Settings::default()
+            }),
+            [
+                "export interface All {",
+                "    kind: string;",
+                "    size: \"10px\" | \"12px\";",
+                "    code: string;",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn number_literals() {
+        let value: Vec<_> = [200, 200, 404, 200, 200, 500, 200, 200, 200, 200, 404, 200]
+            .iter()
+            .enumerate()
+            .map(|(i, status)| ijson!({ "status": status, "id": i, "ratio": 0.5 }))
+            .collect();
+        let settings = Settings {
+            number_literal_max_values: 8,
+            ..Settings::default()
+        };
+        let typing = Typing::from_items_with_settings("All", value.clone(), &settings);
+
+        self::assert_eq!(
+            typing.as_string(Strategy::Family),
+            [
+                "export interface All {",
+                "    status: 200 | 404 | 500;",
+                "    id: number;",
+                "    ratio: number;",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+
+        let typing = Typing::from_items("All", value);
+        assert!(typing
+            .as_string(Strategy::Family)
+            .contains("    status: number;"));
+    }
+
+    #[test]
+    fn discriminated_unions() {
+        let value = ijson!({
+            "events": [
+                { "type": "click", "x": 1, "y": 2 },
+                { "type": "scroll", "delta": 3 },
+                { "type": "click", "x": 4, "y": 5 },
+            ]
+        });
+        let settings = Settings {
+            discriminated_unions: true,
+            ..Settings::default()
+        };
+        let typing = Typing::from_items_with_settings("All", vec![value], &settings);
+
+        self::assert_eq!(
+            typing.as_string(Strategy::Family),
+            [
+                "export interface All {",
+                "    events: Array<AllEventsClick | AllEventsScroll>;",
+                "}",
+                "",
+                "export interface AllEventsClick {",
+                "    type: \"click\";",
+                "    x: number;",
+                "    y: number;",
+                "}",
+                "",
+                "export interface AllEventsScroll {",
+                "    type: \"scroll\";",
+                "    delta: number;",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn discriminated_root() {
+        let settings = Settings {
+            discriminated_unions: true,
+            ..Settings::default()
+        };
+        let typing = Typing::from_items_with_settings(
+            "All",
+            ijson!([
+                { "type": "click", "x": 1 },
+                { "type": "scroll", "delta": 3 },
+                { "type": "click", "x": 4 },
+            ]),
+            &settings,
+        );
+
+        self::assert_eq!(
+            typing.as_string(Strategy::Family),
+            [
+                "export type All = AllClick | AllScroll;",
+                "",
+                "export interface AllClick {",
+                "    type: \"click\";",
+                "    x: number;",
+                "}",
+                "",
+                "export interface AllScroll {",
+                "    type: \"scroll\";",
+                "    delta: number;",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+
+        let typing = Typing::from_items(
+            "All",
+            ijson!([{ "type": "click" }, { "type": "scroll" }, { "type": "click" }]),
+        );
+        self::assert_eq!(
+            typing.as_string(Strategy::Family),
+            [
+                "export interface All {",
+                "    type: \"click\" | \"scroll\";",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn variant_names() {
+        let value = ijson!({
+            "events": [
+                { "type": "user_created", "id": 1 },
+                { "type": "userCreated", "user_id": 2 },
+                { "type": "user-created", "userId": 3 },
+                { "type": "user_created", "id": 4 },
+            ]
+        });
+        let settings = Settings {
+            discriminated_unions: true,
+            ..Settings::default()
+        };
+        let typing = Typing::from_items_with_settings("All", vec![value], &settings);
+
+        let objects: Vec<_> = typing
+            .object_paths()
+            .into_iter()
+            .map(|(nx, _)| typing.graph[nx].as_object().unwrap().to_string())
+            .collect();
+        self::assert_eq!(
+            objects,
+            vec![
+                "All",
+                "EventsUserCreated",
+                "EventsUserCreated2",
+                "EventsUserCreated3",
+            ]
+        );
+    }
+
+    #[test]
+    fn single_sample_array() {
+        let typing = Typing::from_items("All", vec![ijson!({ "point": [1, "a"] })]);
+
+        self::assert_eq!(
+            typing.as_string(Strategy::Family),
+            "export interface All {\n    point: Array<number | string>;\n}\n"
+        );
+    }
+
+    #[test]
+    fn unlimited_literal_values() {
+        let code = |i: u8| format!("{}{}", (b'a' + i / 26) as char, (b'a' + i % 26) as char);
+        let value: Vec<_> = (0..80).map(|i| ijson!({ "code": code(i % 40) })).collect();
+        let typing = Typing::from_items("All", value);
+        let union = (0..40).map(|i| format!("{:?}", code(i))).join(" | ");
+
+        self::assert_eq!(
+            typing.as_string(Strategy::Family),
+            format!("export interface All {{\n    code: {};\n}}\n", union)
+        );
+    }
 }
@@ -3,6 +3,8 @@ use std::convert::From;
 use itertools::Itertools;
 use unicode_segmentation::UnicodeSegmentation;
 
+use crate::regex;
+
 pub fn upper_first(s: &str) -> String {
     let mut graphemes = s.graphemes(true);
     match graphemes.next() {
@@ -12,10 +14,9 @@ pub fn upper_first(s: &str) -> String {
 }
 
 pub fn add_indentation(indentation: &str, s: &str) -> String {
-    s.split("\n")
-        .into_iter()
+    s.split('\n')
         .map(|s| {
-            if s.trim().len() > 0 {
+            if !s.trim().is_empty() {
                 String::from(indentation) + s
             } else {
                 String::from(s)
@@ -24,6 +25,21 @@ pub fn add_indentation(indentation: &str, s: &str) -> String {
         .join("\n")
 }
 
+/// Typescript property name of `key`, quoted with `delimiter` and escaped unless it is an
+/// identifier
+pub fn property_key(key: &str, delimiter: &str) -> String {
+    if regex::IDENTIFIER.is_match(key).unwrap_or(false) {
+        return String::from(key);
+    }
+
+    let escaped = key
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace(delimiter, &format!("\\{}", delimiter));
+
+    format!("{0}{1}{0}", delimiter, escaped)
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -31,6 +47,16 @@ mod tests {
         assert_eq!(super::upper_first("test"), String::from("Test"));
     }
 
+    #[test]
+    fn property_key() {
+        assert_eq!(super::property_key("name", "\""), "name");
+        assert_eq!(super::property_key("$id", "\""), "$id");
+        assert_eq!(super::property_key("a-b", "\""), "\"a-b\"");
+        assert_eq!(super::property_key("a\"b", "\""), "\"a\\\"b\"");
+        assert_eq!(super::property_key("a'b\\c", "'"), "'a\\'b\\\\c'");
+        assert_eq!(super::property_key("", "\""), "\"\"");
+    }
+
     #[test]
     fn add_indentation() {
         assert_eq!(
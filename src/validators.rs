@@ -3,6 +3,8 @@ use std::{
     path::Path,
 };
 
+use crate::Settings;
+
 pub fn file_exists(v: &OsStr) -> Result<(), OsString> {
     if Path::new(v).exists() {
         Ok(())
@@ -10,3 +12,34 @@ pub fn file_exists(v: &OsStr) -> Result<(), OsString> {
         Err(OsString::from("The passed file path does not exists"))
     }
 }
+
+/// Accepts existing paths, glob patterns and `-` for stdin
+pub fn is_input(v: &OsStr) -> Result<(), OsString> {
+    if v == "-" || Path::new(v).exists() {
+        return Ok(());
+    }
+
+    match v.to_str().map(glob::Pattern::new) {
+        Some(Ok(_)) if v.to_string_lossy().contains(&['*', '?', '['][..]) => Ok(()),
+        _ => Err(OsString::from(
+            "The passed path does not exists and is not a glob pattern",
+        )),
+    }
+}
+
+pub fn is_number(v: String) -> Result<(), String> {
+    match v.parse::<usize>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(String::from("The passed value is not a positive number")),
+    }
+}
+
+pub fn is_typescript_version(v: String) -> Result<(), String> {
+    if v == "latest" || Settings::parse_typescript_version(&v).is_some() {
+        Ok(())
+    } else {
+        Err(String::from(
+            "The passed value is not latest or a typescript version",
+        ))
+    }
+}
@@ -0,0 +1,126 @@
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+use pretty_assertions::assert_eq;
+
+/// Runs the binary with `args`, piping `input` through its stdin
+fn jsontypings(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_jsontypings"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn stdin_to_stdout() {
+    let output = jsontypings(&["-", "-o", "-"], r#"[{ "a": 1 }, { "a": 2, "b": "x" }]"#);
+
+    assert!(output.status.success());
+    self::assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        [
+            "export interface All {",
+            "    a: number;",
+            "    b?: string;",
+            "}",
+            "",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn stdin_json_lines() {
+    let output = jsontypings(
+        &["-", "-o", "-", "--family"],
+        "{ \"a\": 1 }\n{ \"a\": null }\n",
+    );
+
+    assert!(output.status.success());
+    self::assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        ["export interface All {", "    a: number | null;", "}", ""].join("\n")
+    );
+}
+
+#[test]
+fn split_to_stdout() {
+    let output = jsontypings(&["-", "--split", "-o", "-"], r#"{ "a": 1 }"#);
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Can't write split modules to stdout"));
+}
+
+#[test]
+fn literal_values_in_declarations() {
+    let output = jsontypings(
+        &["-", "--literal_style", "object", "-o", "index.d.ts"],
+        r#"[{ "a": "x" }, { "a": "x" }, { "a": "y" }]"#,
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("need a .ts output"));
+}
+
+#[test]
+fn literal_values_to_stdout() {
+    let input = r#"[{ "a": "x" }, { "a": "x" }, { "a": "y" }]"#;
+    let output = jsontypings(&["-", "--literal_style", "enum", "-o", "-"], input);
+
+    assert!(output.status.success());
+    self::assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        [
+            "export interface All {",
+            "    a: All.A;",
+            "}",
+            "",
+            "export namespace All {",
+            "    export enum A {",
+            "        X = \"x\",",
+            "        Y = \"y\",",
+            "    }",
+            "}",
+            "",
+        ]
+        .join("\n")
+    );
+
+    let output = jsontypings(
+        &["-", "--literal_style", "enum", "--split", "-o", "-"],
+        input,
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Can't write split modules to stdout"));
+}
+
+#[test]
+fn invalid_typescript_version() {
+    let output = jsontypings(&["-", "-t", "4.x", "-o", "-"], r#"{ "a": 1 }"#);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("not latest or a typescript version"));
+}